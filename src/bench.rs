@@ -1,53 +1,242 @@
-use std::collections::HashMap;
-use std::fmt::Debug;
-use std::time::{Duration, SystemTime};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Debug, Display, Formatter};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use crate::export::Record;
+use advent_of_code_2022::solver::{DayEntry, Part, Puzzle};
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+
+    pub fn new(samples: &[Duration]) -> Stats {
+        if samples.is_empty() {
+            return Stats::default();
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+
+        let mean = sorted.iter().sum::<Duration>() / n as u32;
+        let variance = sorted.iter()
+            .map(|d| d.as_secs_f64() - mean.as_secs_f64())
+            .map(|diff| diff * diff)
+            .sum::<f64>() / n as f64;
+
+        Stats {
+            samples: n,
+            min: sorted[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "median {:?} (min {:?}, mean {:?}, stddev {:?})", self.median, self.min, self.mean, self.stddev)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DayTimes {
+    pub parse: Stats,
+    pub parts: BTreeMap<Part, Stats>,
+    pub total: Stats,
+}
+
+#[derive(Debug, Default)]
+struct Samples {
+    parse: Vec<Duration>,
+    parts: BTreeMap<Part, Vec<Duration>>,
+    total: Vec<Duration>,
+}
+
+impl Samples {
+
+    fn into_times(self) -> DayTimes {
+        DayTimes {
+            parse: Stats::new(&self.parse),
+            parts: self.parts.iter().map(|(part, samples)| (*part, Stats::new(samples))).collect(),
+            total: Stats::new(&self.total),
+        }
+    }
+
+}
 
 #[derive(Debug)]
 pub struct Bench {
-    times: HashMap<usize, Duration>,
+    warmup: usize,
+    runs: usize,
+    times: HashMap<usize, DayTimes>,
 }
 
 impl Bench {
-    pub fn new() -> Bench {
+    pub fn new(warmup: usize, runs: usize) -> Bench {
         Bench {
+            warmup,
+            runs: runs.max(1),
             times: HashMap::new(),
         }
     }
 
-    pub fn run_day<F: FnOnce()>(&mut self, day: usize, f: F) {
+    fn print_answers(puzzle: &dyn Puzzle, parts: &[Part]) {
+        for part in parts {
+            match puzzle.solve(*part) {
+                Ok(answer) if answer.contains('\n') => println!("part{}:\n{}", part, answer),
+                Ok(answer) => println!("part{}: {}", part, answer),
+                Err(e) => println!("part{} failed: {}", part, e),
+            }
+        }
+    }
+
+    fn measure(entry: &DayEntry, input: &str, parts: &[Part], samples: &mut Samples) {
+        let start = Instant::now();
+        let puzzle = match entry.parse(black_box(input)) {
+            Ok(puzzle) => puzzle,
+            Err(_) => return,
+        };
+        let parsed = start.elapsed();
+        samples.parse.push(parsed);
+
+        let mut total = parsed;
+        for part in parts {
+            let start = Instant::now();
+            let _ = black_box(puzzle.solve(*part));
+            let elapsed = start.elapsed();
+
+            samples.parts.entry(*part).or_default().push(elapsed);
+            total += elapsed;
+        }
+        samples.total.push(total);
+    }
+
+    pub fn run_day(&mut self, entry: &DayEntry, input: &str, parts: &[Part]) {
+        let day = entry.day();
         println!("day{}", day);
-        let start = SystemTime::now();
-        f();
-        let elapsed = start.elapsed().unwrap_or(Duration::default());
-        println!("day{} took {:?}", day, &elapsed);
+
+        // the run printing the answers is not timed and counts as the first warmup run
+        match entry.parse(input) {
+            Ok(puzzle) => Self::print_answers(puzzle.as_ref(), parts),
+            Err(e) => {
+                println!("cannot parse input: {}", e);
+                println!();
+                return;
+            }
+        }
+
+        for _ in 1..self.warmup {
+            Self::measure(entry, input, parts, &mut Samples::default());
+        }
+
+        let mut samples = Samples::default();
+        for _ in 0..self.runs {
+            Self::measure(entry, input, parts, &mut samples);
+        }
+
+        let times = samples.into_times();
+        println!("day{} took {}", day, times.total);
         println!();
-        self.times.insert(day, elapsed);
+        self.times.insert(day, times);
     }
 
     pub fn print_times(&self) {
         let mut keys: Vec<&usize> = self.times.keys().collect();
         keys.sort();
         for key in keys {
-            println!("day{}: {:?}", key, self.times[key]);
+            let times = &self.times[key];
+            println!("day{}: {}", key, times.total);
+            println!("  parse: {}", times.parse);
+            for (part, stats) in &times.parts {
+                println!("  part{}: {}", part, stats);
+            }
         }
     }
 
     pub fn print_slowest_days<const N: usize>(&self) {
-        let mut values: Vec<(&usize, &Duration)> = self.times.iter().collect();
-        values.sort_by(|(_, d1), (_, d2)| d2.cmp(d1));
+        let mut values: Vec<(&usize, &DayTimes)> = self.times.iter().collect();
+        values.sort_by_key(|(_, t)| std::cmp::Reverse(t.total.median));
 
-        println!("{} slowest days:", N);
-        for (day, dur) in values.iter().take(N) {
-            println!("day{}: {:?}", day, dur);
+        println!("{} slowest days (by median):", N);
+        for (day, times) in values.iter().take(N) {
+            println!("day{}: {:?}", day, times.total.median);
         }
     }
 
+    pub fn records(&self) -> Vec<Record> {
+        let mut days: Vec<&usize> = self.times.keys().collect();
+        days.sort();
+
+        days.into_iter().flat_map(|day| {
+            let times = &self.times[day];
+            let record = |phase: String, stats: &Stats| Record { day: *day, phase, stats: *stats };
+
+            std::iter::once(record("parse".to_string(), &times.parse))
+                .chain(times.parts.iter().map(move |(part, stats)| record(format!("part{}", part), stats)))
+                .chain(std::iter::once(record("total".to_string(), &times.total)))
+                .collect::<Vec<_>>()
+        }).collect()
+    }
+
     pub fn print_total_time(&self) {
-        let mut total_time = Duration::default();
-        for duration in self.times.values() {
-            total_time += *duration;
-        }
-        println!("total time elapsed: {:?}", total_time);
+        let total_time: Duration = self.times.values().map(|t| t.total.median).sum();
+        println!("total time elapsed (sum of medians, {} runs each): {:?}", self.runs, total_time);
     }
 }
 
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples: Vec<Duration> = [4, 1, 3, 2].into_iter().map(Duration::from_millis).collect();
+        let stats = Stats::new(&samples);
+
+        assert_eq!(4, stats.samples);
+        assert_eq!(Duration::from_millis(1), stats.min);
+        assert_eq!(Duration::from_micros(2500), stats.median);
+        assert_eq!(Duration::from_micros(2500), stats.mean);
+        assert_eq!(1118, stats.stddev.as_micros());
+
+        let stats = Stats::new(&samples[..3]);
+        assert_eq!(Duration::from_millis(3), stats.median);
+
+        assert_eq!(Stats::default(), Stats::new(&[]));
+    }
+
+    #[test]
+    fn test_run_day() {
+        let entry = advent_of_code_2022::solver::find_day(1).unwrap();
+        let mut bench = Bench::new(2, 3);
+
+        bench.run_day(&entry, include_str!("../res/day1-calories_example.txt"), &[Part::One, Part::Two]);
+
+        let times = &bench.times[&1];
+        assert_eq!(3, times.parse.samples);
+        assert_eq!(3, times.total.samples);
+        assert_eq!(vec![Part::One, Part::Two], times.parts.keys().copied().collect::<Vec<_>>());
+        assert!(times.total.min >= times.parse.min);
+
+        let phases: Vec<String> = bench.records().into_iter().map(|r| r.phase).collect();
+        assert_eq!(vec!["parse", "part1", "part2", "total"], phases);
+    }
+
+}
@@ -0,0 +1,285 @@
+use std::borrow::Cow;
+use std::fs;
+use std::io::{stdin, Read};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use crate::export::Threshold;
+use advent_of_code_2022::solver::Part;
+use advent_of_code_2022::utils::Error;
+
+pub const USAGE: &str = "\
+usage: advent-of-code-2022 [options]
+
+options:
+  -d, --day <days>     days to run, e.g. `12`, `1-10` or `1,3,5-7` (default: all)
+  -p, --part <part>    part to run, `1` or `2` (default: both)
+  -i, --input <path>   read the input from <path> instead of the compiled-in
+                       input, `-` reads from stdin (requires a single day)
+  -r, --runs <n>       timed runs per day (default: 5)
+  -w, --warmup <n>     untimed warmup runs per day, including the run printing
+                       the answers (default: 1)
+      --export <path>  write the timings to <path>, `.json` or `.csv`
+      --compare <path> compare the timings with a previous export and fail if
+                       a day got slower than the threshold
+      --threshold <n>  allowed slowdown in percent for --compare (default: 10)
+      --verify         check the answers for the real and example inputs
+                       against the expected answers
+      --answers <path> expected answers for --verify (default: res/answers.txt)
+  -h, --help           print this help";
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Input {
+    Default,
+    Stdin,
+    File(String),
+}
+
+impl Input {
+
+    pub fn read<'a>(&self, default_input: &'a str) -> Result<Cow<'a, str>, Error> {
+        match self {
+            Input::Default => Ok(Cow::Borrowed(default_input)),
+            Input::Stdin => {
+                let mut buffer = String::new();
+                stdin().read_to_string(&mut buffer).map_err(|e| Error::io("cannot read stdin", e))?;
+                Ok(Cow::Owned(buffer))
+            }
+            Input::File(path) => {
+                let content = fs::read_to_string(path).map_err(|e| Error::io(format!("cannot read {}", path), e))?;
+                Ok(Cow::Owned(content))
+            }
+        }
+    }
+
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DaySelection(Vec<RangeInclusive<usize>>);
+
+impl DaySelection {
+
+    pub fn contains(&self, day: usize) -> bool {
+        let DaySelection(ranges) = self;
+        ranges.iter().any(|r| r.contains(&day))
+    }
+
+    pub fn single_day(&self) -> Option<usize> {
+        match &self.0[..] {
+            [range] if range.start() == range.end() => Some(*range.start()),
+            _ => None,
+        }
+    }
+
+}
+
+impl FromStr for DaySelection {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut ranges = Vec::new();
+
+        for spec in s.split(',').map(str::trim) {
+            let range = match spec.split_once('-') {
+                Some((start, end)) => start.trim().parse()?..=end.trim().parse()?,
+                None => {
+                    let day = spec.parse()?;
+                    day..=day
+                }
+            };
+
+            if range.is_empty() {
+                return Err(Error::parse(format!("invalid day range: {}", spec)));
+            }
+
+            ranges.push(range);
+        }
+
+        Ok(DaySelection(ranges))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Args {
+    pub days: Option<DaySelection>,
+    pub parts: Vec<Part>,
+    pub input: Input,
+    pub runs: usize,
+    pub warmup: usize,
+    pub export: Option<String>,
+    pub compare: Option<String>,
+    pub threshold: Threshold,
+    pub verify: bool,
+    pub answers: Option<String>,
+    pub help: bool,
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Args {
+            days: None,
+            parts: vec![Part::One, Part::Two],
+            input: Input::Default,
+            runs: 5,
+            warmup: 1,
+            export: None,
+            compare: None,
+            threshold: Threshold::default(),
+            verify: false,
+            answers: None,
+            help: false,
+        }
+    }
+}
+
+impl Args {
+
+    pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Args, Error> {
+        let mut result = Args::default();
+        let mut part = None;
+
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+                _ => (arg, None),
+            };
+
+            let mut value = || inline_value.clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| Error::parse(format!("missing value for {}", flag)));
+
+            match flag.as_str() {
+                "-d" | "--day" => {
+                    let selection: DaySelection = value()?.parse()?;
+                    match &mut result.days {
+                        None => result.days = Some(selection),
+                        Some(DaySelection(ranges)) => ranges.extend(selection.0),
+                    }
+                }
+                "-p" | "--part" => part = Some(value()?.parse()?),
+                "-i" | "--input" => {
+                    let path = value()?;
+                    result.input = if path == "-" {
+                        Input::Stdin
+                    } else {
+                        Input::File(path)
+                    };
+                }
+                "-r" | "--runs" => result.runs = value()?.parse()?,
+                "-w" | "--warmup" => result.warmup = value()?.parse()?,
+                "--export" => result.export = Some(value()?),
+                "--compare" => result.compare = Some(value()?),
+                "--threshold" => result.threshold = value()?.parse()?,
+                "--verify" => result.verify = true,
+                "--answers" => result.answers = Some(value()?),
+                "-h" | "--help" => result.help = true,
+                other => return Err(Error::parse(format!("unknown argument: {}", other))),
+            }
+        }
+
+        if let Some(part) = part {
+            result.parts = vec![part];
+        }
+
+        if result.input != Input::Default && result.days.as_ref().and_then(DaySelection::single_day).is_none() {
+            return Err(Error::parse("an input override requires a single day (--day N)"));
+        }
+
+        if result.runs == 0 {
+            return Err(Error::parse("--runs must be at least 1"));
+        }
+
+        if result.verify && result.input != Input::Default {
+            return Err(Error::parse("--verify cannot be combined with an input override"));
+        }
+
+        Ok(result)
+    }
+
+    pub fn selects_day(&self, day: usize) -> bool {
+        self.days.as_ref().is_none_or(|d| d.contains(day))
+    }
+
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, Error> {
+        Args::parse(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn test_defaults() {
+        let args = parse(&[]).unwrap();
+
+        assert_eq!(Args::default(), args);
+        assert!(args.selects_day(1));
+        assert!(args.selects_day(25));
+        assert!(args.parts.contains(&Part::One));
+        assert!(args.parts.contains(&Part::Two));
+    }
+
+    #[test]
+    fn test_day_selection() {
+        let args = parse(&["--day", "1-3,7", "-d", "12"]).unwrap();
+
+        let selected: Vec<usize> = (1..=25).filter(|d| args.selects_day(*d)).collect();
+        assert_eq!(vec![1, 2, 3, 7, 12], selected);
+
+        assert!(parse(&["--day", "5-3"]).is_err());
+        assert!(parse(&["--day", "x"]).is_err());
+        assert!(parse(&["--day"]).is_err());
+    }
+
+    #[test]
+    fn test_part_and_input() {
+        let args = parse(&["--day=12", "--part", "2", "--input", "some/file.txt"]).unwrap();
+
+        assert!(!args.parts.contains(&Part::One));
+        assert!(args.parts.contains(&Part::Two));
+        assert_eq!(Input::File("some/file.txt".to_string()), args.input);
+
+        let args = parse(&["-d", "3", "-i", "-"]).unwrap();
+        assert_eq!(Input::Stdin, args.input);
+
+        assert!(parse(&["--part", "3"]).is_err());
+        assert!(parse(&["--input", "file.txt"]).is_err());
+        assert!(parse(&["--day", "1-2", "--input", "file.txt"]).is_err());
+        assert!(parse(&["--verbose"]).is_err());
+    }
+
+    #[test]
+    fn test_runs() {
+        let args = parse(&["--runs", "20", "-w", "3"]).unwrap();
+
+        assert_eq!(20, args.runs);
+        assert_eq!(3, args.warmup);
+
+        assert!(parse(&["--runs", "0"]).is_err());
+        assert!(parse(&["--runs", "-1"]).is_err());
+    }
+
+    #[test]
+    fn test_export_and_compare() {
+        let args = parse(&["--export", "times.json", "--compare=old.csv", "--threshold", "25"]).unwrap();
+
+        assert_eq!(Some("times.json".to_string()), args.export);
+        assert_eq!(Some("old.csv".to_string()), args.compare);
+        assert_eq!(Threshold(25.0), args.threshold);
+
+        assert!(parse(&["--threshold", "fast"]).is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let args = parse(&["--verify", "--answers=other.txt", "-d", "1-5"]).unwrap();
+
+        assert!(args.verify);
+        assert_eq!(Some("other.txt".to_string()), args.answers);
+
+        assert!(parse(&["--verify", "-d", "1", "-i", "file.txt"]).is_err());
+    }
+
+}
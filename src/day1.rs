@@ -1,10 +1,13 @@
 
-pub fn read_elf_calories(calories_data: &str) -> Vec<Vec<u32>> {
+use crate::Error;
+use crate::solver::Solver;
+
+pub fn read_elf_calories(calories_data: &str) -> Result<Vec<Vec<u32>>, Error> {
     let mut all_data = Vec::new();
 
 
     let mut cur_data = Vec::new();
-    for line in calories_data.lines() {
+    for (i, line) in calories_data.lines().enumerate() {
         let line = line.trim_end();
         if line.is_empty() {
             all_data.push(cur_data.clone());
@@ -12,7 +15,7 @@ pub fn read_elf_calories(calories_data: &str) -> Vec<Vec<u32>> {
             continue;
         }
 
-        let calories: u32 = line.parse().expect("invalid calories line");
+        let calories: u32 = line.parse().map_err(|e| Error::from(e).with_line(i + 1))?;
         cur_data.push(calories);
     }
 
@@ -20,17 +23,16 @@ pub fn read_elf_calories(calories_data: &str) -> Vec<Vec<u32>> {
         all_data.push(cur_data.clone());
     }
 
-    all_data
+    Ok(all_data)
 }
 
 pub fn accumulate_per_elf(elf_calories: &Vec<Vec<u32>>) -> Vec<u32> {
     elf_calories.iter().map(|v| v.iter().sum()).collect()
 }
 
-pub fn find_most_calories_elf(accumulated_calories: &Vec<u32>) -> usize {
+pub fn find_most_calories_elf(accumulated_calories: &Vec<u32>) -> Option<usize> {
     let most_calories_entry = accumulated_calories.iter().enumerate().max_by_key(|e| e.1);
-    let most_calories_entry = most_calories_entry.expect("no thicc boy?");
-    most_calories_entry.0
+    most_calories_entry.map(|e| e.0)
 }
 
 pub fn find_topk_calories_elfs(accumulated_calories: &Vec<u32>, k: usize) -> Vec<usize> {
@@ -39,6 +41,28 @@ pub fn find_topk_calories_elfs(accumulated_calories: &Vec<u32>, k: usize) -> Vec
     calories.iter().rev().take(k).map(|v| v.0).collect()
 }
 
+pub struct Day1;
+
+impl Solver for Day1 {
+    type Puzzle = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Puzzle, Error> {
+        Ok(accumulate_per_elf(&read_elf_calories(input)?))
+    }
+
+    fn part1(accumulated_calories: &Self::Puzzle) -> Result<Self::Answer1, Error> {
+        let most_calories_elf_idx = find_most_calories_elf(accumulated_calories).ok_or_else(|| Error::invalid_state("no thicc boy?"))?;
+        Ok(accumulated_calories[most_calories_elf_idx])
+    }
+
+    fn part2(accumulated_calories: &Self::Puzzle) -> Result<Self::Answer2, Error> {
+        let top3_elf_idxs = find_topk_calories_elfs(accumulated_calories, 3);
+        Ok(top3_elf_idxs.iter().map(|v| accumulated_calories[*v]).sum())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -47,15 +71,17 @@ mod test {
     fn test_load_aoc2022_data() {
         let data = include_str!("../res/day1-calories_example.txt");
 
-        let calorie_data = read_elf_calories(data);
+        let calorie_data = read_elf_calories(data).unwrap();
 
         println!("{:#?}", calorie_data);
+
+        assert_eq!(Some(4), read_elf_calories("1\n\n2\nx\n").unwrap_err().line());
     }
 
     #[test]
     fn test_per_elf_data_aoc2022_data() {
         let data = include_str!("../res/day1-calories_example.txt");
-        let calorie_data = read_elf_calories(data);
+        let calorie_data = read_elf_calories(data).unwrap();
         let accumulated_data = accumulate_per_elf(&calorie_data);
 
         println!("{:#?}", accumulated_data);
@@ -64,9 +90,9 @@ mod test {
     #[test]
     fn test_find_most_calories_elf() {
         let data = include_str!("../res/day1-calories_example.txt");
-        let calorie_data = read_elf_calories(data);
+        let calorie_data = read_elf_calories(data).unwrap();
         let accumulated_data = accumulate_per_elf(&calorie_data);
-        let most_calories_elf_idx = find_most_calories_elf(&accumulated_data);
+        let most_calories_elf_idx = find_most_calories_elf(&accumulated_data).unwrap();
 
         println!("elf number: {} carries {}", most_calories_elf_idx + 1, accumulated_data[most_calories_elf_idx]);
         assert_eq!(3, most_calories_elf_idx);
@@ -76,7 +102,7 @@ mod test {
     #[test]
     fn test_find_topk_calories_elf() {
         let data = include_str!("../res/day1-calories_example.txt");
-        let calorie_data = read_elf_calories(data);
+        let calorie_data = read_elf_calories(data).unwrap();
         let accumulated_data = accumulate_per_elf(&calorie_data);
         let top3_calories_idxs = find_topk_calories_elfs(&accumulated_data, 3);
 
@@ -1,5 +1,7 @@
 use std::str::FromStr;
 use crate::Error;
+use crate::solver::Solver;
+use crate::utils::{parse_at, parse_lines};
 
 #[derive(Debug)]
 pub enum Instruction {
@@ -22,6 +24,7 @@ impl Instruction {
 pub struct CPU {
     cycle: u32,
     x: i32,
+    screen: String,
 }
 
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Default)]
@@ -38,6 +41,7 @@ impl Default for CPU {
         CPU {
             cycle: 0,
             x: 1,
+            screen: String::new(),
         }
     }
 }
@@ -75,18 +79,22 @@ impl CPU {
         self.cycle >= 20 && (self.cycle - 20) % 40 == 0
     }
 
-    fn gpu_trap(&self) {
+    fn gpu_trap(&mut self) {
         let beam_x = (self.cycle - 1) % 40;
 
+        if beam_x == 0 && self.cycle > 1 {
+            self.screen.push('\n');
+        }
+
         if self.x.abs_diff(beam_x as i32) <= 1 {
-            print!("#");
+            self.screen.push('#');
         } else {
-            print!(".");
+            self.screen.push('.');
         }
+    }
 
-        if self.cycle % 40 == 0 {
-            println!();
-        }
+    pub fn screen(&self) -> &str {
+        &self.screen
     }
 }
 
@@ -98,21 +106,56 @@ impl FromStr for Instruction {
             None => if s == "noop" {
                 Ok(Instruction::Noop)
             } else {
-                Err(Error(format!("Invalid instruction: {}", s)))
+                Err(Error::parse(format!("Invalid instruction: {}", s)))
             },
             Some((cmd, par)) => match cmd {
-                "addx" => Ok(Instruction::AddX(par.parse()?)),
-                _ => Err(Error(format!("Invalid instruction: {}", s)))
+                "addx" => Ok(Instruction::AddX(parse_at(s, par)?)),
+                _ => Err(Error::parse(format!("Invalid instruction: {}", s)))
             }
         }
     }
 }
 
-pub fn get_signal_strength<'a>(lines: impl Iterator<Item = &'a str>) -> Vec<Measurement> {
+pub fn get_signal_strength<'a>(lines: impl Iterator<Item = &'a str>) -> Result<Vec<Measurement>, Error> {
     let mut cpu = CPU::default();
-    lines.filter_map(|line| {
-        cpu.execute(&line.trim_end().parse().unwrap())
-    }).collect()
+    let mut measurements = Vec::new();
+
+    for (i, line) in lines.enumerate() {
+        let instruction = line.trim_end().parse().map_err(|e: Error| e.with_line(i + 1))?;
+        measurements.extend(cpu.execute(&instruction));
+    }
+
+    Ok(measurements)
+}
+
+pub fn run_program(instructions: &[Instruction]) -> (CPU, Vec<Measurement>) {
+    let mut cpu = CPU::default();
+    let measurements = instructions.iter()
+        .filter_map(|instruction| cpu.execute(instruction))
+        .collect();
+    (cpu, measurements)
+}
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Puzzle = Vec<Instruction>;
+    type Answer1 = i32;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Puzzle, Error> {
+        parse_lines(input)
+    }
+
+    fn part1(instructions: &Self::Puzzle) -> Result<Self::Answer1, Error> {
+        let (_, signal_strengths) = run_program(instructions);
+        Ok(signal_strengths.iter().map(|m| m.measurement()).sum())
+    }
+
+    fn part2(instructions: &Self::Puzzle) -> Result<Self::Answer2, Error> {
+        let (cpu, _) = run_program(instructions);
+        Ok(cpu.screen().to_string())
+    }
 }
 
 #[cfg(test)]
@@ -133,15 +176,32 @@ mod test {
 
     #[test]
     fn test_signal_strength_simple() {
-        println!("{:?}", get_signal_strength(EXAMPLE1.lines()));
+        println!("{:?}", get_signal_strength(EXAMPLE1.lines()).unwrap());
+        assert_eq!(Some(2), get_signal_strength("noop\njump 3".lines()).unwrap_err().line());
     }
 
     #[test]
     fn test_signal_strength_example2() {
-        let strengths = get_signal_strength(EXAMPLE2.lines());
+        let strengths = get_signal_strength(EXAMPLE2.lines()).unwrap();
         let unwrapped_strengths: Vec<i32> = strengths.iter().map(|m| m.0).collect();
         assert_eq!(vec![420, 1140, 1800, 2940, 2880, 3960], unwrapped_strengths);
         assert_eq!(13140, unwrapped_strengths.iter().sum());
     }
 
+    #[test]
+    fn test_screen_example2() {
+        let instructions = Day10::parse(EXAMPLE2).unwrap();
+        let (cpu, _) = run_program(&instructions);
+
+        let expected = "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....";
+
+        assert_eq!(expected, cpu.screen());
+    }
+
 }
@@ -1,8 +1,11 @@
 
-use std::{str::FromStr, num::ParseIntError};
+use std::str::FromStr;
 use std::collections::VecDeque;
 
 use crate::Error;
+use crate::solver::Solver;
+use crate::utils::parser::{parse_with, Parser};
+use crate::utils::num::lcm;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Operation {
@@ -27,14 +30,17 @@ impl FromStr for Operation {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = s.split(' ').collect();
+        parse_with(s, |p| {
+            p.literal("new = old")?;
+            let op = p.token()?;
 
-        match &parts[..] {
-            ["new", "=", "old", "*", "old"] => Ok(Operation::Square),
-            ["new", "=", "old", "*", v] => Ok(Operation::Mul(v.parse()?)),
-            ["new", "=", "old", "+", v] => Ok(Operation::Add(v.parse()?)),
-            _ => Err(Error(format!("invalid line: {}", s)))
-        }
+            match op {
+                "*" if p.try_literal("old") => Ok(Operation::Square),
+                "*" => Ok(Operation::Mul(p.integer()?)),
+                "+" => Ok(Operation::Add(p.integer()?)),
+                _ => Err(Error::cannot_parse_at(s, op)),
+            }
+        })
     }
 }
 
@@ -46,14 +52,40 @@ pub struct MonkeyMeta {
     next_monkeys: (usize, usize)
 }
 
-impl Default for MonkeyMeta {
-    fn default() -> Self {
-        Self { starting_items: Default::default(), op: Operation::Add(0), div_test: Default::default(), next_monkeys: Default::default() }
-    }
-}
-
 impl MonkeyMeta {
 
+    fn parse(p: &mut Parser) -> Result<MonkeyMeta, Error> {
+        p.literal("Monkey")?;
+        p.integer::<usize>()?;
+        p.literal(":")?;
+
+        let starting_items = p.field("Starting items", |p| p.separated(",", Parser::integer))?;
+        let op = p.field("Operation", Parser::parse)?;
+        let div_test = p.field("Test", |p| {
+            p.literal("divisible by")?;
+            let divisor = *p;
+            match p.integer()? {
+                0 => Err(divisor.error("cannot test divisibility by 0")),
+                div_test => Ok(div_test),
+            }
+        })?;
+        let if_true = p.field("If true", |p| {
+            p.literal("throw to monkey")?;
+            p.integer()
+        })?;
+        let if_false = p.field("If false", |p| {
+            p.literal("throw to monkey")?;
+            p.integer()
+        })?;
+
+        Ok(MonkeyMeta {
+            starting_items,
+            op,
+            div_test,
+            next_monkeys: (if_true, if_false),
+        })
+    }
+
     pub fn start_eval(&self) -> MonkeyState {
         let items = self.starting_items.clone().into();
         MonkeyState {
@@ -72,48 +104,8 @@ impl FromStr for AllMonkeyMeta {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-
-        let mut result = Vec::new();
-        let mut accu = MonkeyMeta::default();
-
-        for line in s.lines().map(|l| l.trim()) {
-            if line.is_empty() {
-                result.push(accu);
-                accu = MonkeyMeta::default();
-            }
-
-            let vars = line.split_once(":");
-            if let None = vars {
-                continue;
-            }
-
-            let (op, par) = vars.unwrap();
-
-            // println!("op: .{}. par: .{}.", op, par);
-
-            match op {
-                "Starting items" => {
-                    let items: Result<Vec<u64>, ParseIntError> = par.split(",").map(|v| v.trim().parse()).collect();
-                    accu.starting_items = items?;
-                },
-                "Operation" => {
-                    accu.op = par.trim().parse()?;
-                },
-                "Test" => {
-                    accu.div_test = par.split_whitespace().last().ok_or(Error(format!("invalid line: {}", line)))?.parse()?;
-                },
-                "If true" => {
-                    accu.next_monkeys.0 = par.split_whitespace().last().ok_or(Error(format!("invalid line: {}", line)))?.parse()?;
-                },
-                "If false" => {
-                    accu.next_monkeys.1 = par.split_whitespace().last().ok_or(Error(format!("invalid line: {}", line)))?.parse()?;
-                },
-                _ => {},
-            }
-
-        }
-
-        Ok(AllMonkeyMeta(result))
+        let monkeys = parse_with(s, |p| p.blocks(MonkeyMeta::parse))?;
+        Ok(AllMonkeyMeta(monkeys))
     }
 
 
@@ -123,7 +115,7 @@ impl AllMonkeyMeta {
 
     pub fn get_worry_mod(&self) -> ModWorry {
         let AllMonkeyMeta(metas) = self;
-        let modulo = metas.iter().map(|f| f.div_test).fold(1, |a, o| a * o);
+        let modulo = metas.iter().map(|f| f.div_test).fold(1, lcm);
         ModWorry(modulo)
     }
 
@@ -166,15 +158,10 @@ impl Worry for ModWorry {
 impl<'a> MonkeyState<'a> {
 
     pub fn eval_one_item(&mut self, worry: &impl Worry) -> Option<(u64, &usize)> {
-        let front = self.items.pop_front();
-
-        if let None = front {
-            return None;
-        }
+        let item = self.items.pop_front()?;
 
         self.inspect_counter += 1;
 
-        let item = front.unwrap();
         let new_worry = self.meta.op.eval(&item);
         let new_worry = worry.apply_worry(new_worry);
 
@@ -206,7 +193,7 @@ impl AllMonkeyMeta {
 
 impl<'a> AllMonkeys<'a> {
 
-    pub fn eval_round(&mut self, worry: &impl Worry) {
+    pub fn eval_round(&mut self, worry: &impl Worry) -> Result<(), Error> {
         let AllMonkeys(monkeys) = self;
 
         let mut add_items: Vec<Vec<u64>> = vec![Vec::default(); monkeys.len()];
@@ -224,7 +211,9 @@ impl<'a> AllMonkeys<'a> {
                 match result {
                     None => break,
                     Some((worry, next)) => {
-                        add_items[*next].push(worry);
+                        add_items.get_mut(*next)
+                            .ok_or_else(|| Error::invalid_state(format!("monkey {} throws to unknown monkey {}", i, next)))?
+                            .push(worry);
                     }
                 }
             }
@@ -236,18 +225,22 @@ impl<'a> AllMonkeys<'a> {
             }
         }
 
+        Ok(())
     }
 
-    pub fn eval_rounds<const ROUNDS: usize>(&mut self, worry: &impl Worry) {
+    pub fn eval_rounds<const ROUNDS: usize>(&mut self, worry: &impl Worry) -> Result<(), Error> {
         for _ in 0..ROUNDS {
-            self.eval_round(worry);
+            self.eval_round(worry)?;
         }
+        Ok(())
     }
 
-    pub fn find_most_active<const N: usize>(&self) -> Vec<&u64> {
+    pub fn find_most_active<const N: usize>(&self) -> Result<Vec<&u64>, Error> {
         let AllMonkeys(monkeys) = self;
 
-        assert!(N <= monkeys.len());
+        if N > monkeys.len() {
+            return Err(Error::invalid_state(format!("expected at least {} monkeys, got {}", N, monkeys.len())));
+        }
 
         let mut inspections: Vec<&u64> = monkeys.iter().map(|m| &m.inspect_counter).collect();
         inspections.sort();
@@ -255,11 +248,39 @@ impl<'a> AllMonkeys<'a> {
 
         let _ = inspections.split_off(N);
 
-        inspections
+        Ok(inspections)
     }
 
 }
 
+pub struct Day11;
+
+impl Solver for Day11 {
+    type Puzzle = AllMonkeyMeta;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Puzzle, Error> {
+        input.parse()
+    }
+
+    fn part1(monkey_meta: &Self::Puzzle) -> Result<Self::Answer1, Error> {
+        let mut monkey_state = monkey_meta.start_eval();
+        monkey_state.eval_rounds::<20>(&ReduceWorry)?;
+
+        let active = monkey_state.find_most_active::<2>()?;
+        Ok(active.iter().fold(1, |a, i| a * *i))
+    }
+
+    fn part2(monkey_meta: &Self::Puzzle) -> Result<Self::Answer2, Error> {
+        let mut monkey_state = monkey_meta.start_eval();
+        monkey_state.eval_rounds::<10_000>(&monkey_meta.get_worry_mod())?;
+
+        let active = monkey_state.find_most_active::<2>()?;
+        Ok(active.iter().fold(1, |a, i| a * *i))
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -286,6 +307,21 @@ mod test {
         assert!(op_err.is_err());
     }
 
+    #[test]
+    fn test_parse_error_location() {
+        let input = EXAMPLE.replace("Test: divisible by 13", "Test: divisible by x3");
+        let error = input.parse::<AllMonkeyMeta>().unwrap_err();
+
+        assert_eq!(Some(18), error.line());
+        assert_eq!(Some(22), error.column());
+
+        let input = EXAMPLE.replace("Test: divisible by 13", "Test: divisible by 0");
+        let error = input.parse::<AllMonkeyMeta>().unwrap_err();
+
+        assert_eq!(Some(18), error.line());
+        assert_eq!(Some(22), error.column());
+    }
+
     #[test]
     fn test_parse_monkey_meta() {
         let allmeta: AllMonkeyMeta = EXAMPLE.parse().unwrap();
@@ -298,9 +334,9 @@ mod test {
 
         let mut all_monkeys: AllMonkeys = allmeta.start_eval();
 
-        all_monkeys.eval_rounds::<20>(&ReduceWorry);
+        all_monkeys.eval_rounds::<20>(&ReduceWorry).unwrap();
 
-        let actives = all_monkeys.find_most_active::<2>();
+        let actives = all_monkeys.find_most_active::<2>().unwrap();
         println!("actives: {:?}", actives);
 
         let business = actives.iter().fold(1, |a, i| a * *i);
@@ -317,9 +353,9 @@ mod test {
 
         let worry_mod = allmeta.get_worry_mod();
 
-        all_monkeys.eval_rounds::<10_000>(&worry_mod);
+        all_monkeys.eval_rounds::<10_000>(&worry_mod).unwrap();
 
-        let actives = all_monkeys.find_most_active::<2>();
+        let actives = all_monkeys.find_most_active::<2>().unwrap();
         println!("actives: {:?}", actives);
 
         let business = actives.iter().fold(1, |a, i| a * *i);
@@ -1,26 +1,26 @@
-
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 use crate::Error;
-use crate::utils::bfs::{Bfs, Graph};
-
-type Vec2 = (u8, u8);
+use crate::solver::Solver;
+use crate::utils::bfs::{Bfs, PropertyFilter};
+use crate::utils::grid::{Grid, GridPos};
+use crate::utils::vec2::Vec2;
 
 #[derive(Debug)]
 pub struct HeightMap {
-    map: Vec<Vec<u8>>,
-    width: u8,
-    height: u8,
-    start_pos: Vec2,
-    end_pos: Vec2,
+    map: Grid<u8>,
+    start_pos: GridPos,
+    end_pos: GridPos,
 }
 
-fn parse_height(c: &char) -> u8 {
+fn parse_height(c: &char) -> Result<u8, Error> {
     match c {
-        'S' => 1,
-        'E' => 26,
-        c => (*c as u8 - 'a' as u8) + 1
+        'S' => Ok(1),
+        'E' => Ok(26),
+        'a'..='z' => Ok((*c as u8 - 'a' as u8) + 1),
+        c => Err(Error::parse(format!("invalid height: {}", c))),
     }
 }
 
@@ -28,141 +28,135 @@ impl FromStr for HeightMap {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut width: Option<u8> = None;
-        let mut map: Vec<Vec<u8>> = Default::default();
-        let mut start_pos: Option<(u8, u8)> = None;
-        let mut end_pos: Option<(u8, u8)> = None;
-
-        for line in s.lines() {
-            let line = line.trim_end();
-
-            if width.is_none() {
-                width = Some(line.len() as u8);
-            }
-            let mut row = Vec::new();
-
-            for (i, c) in line.chars().enumerate() {
-                row.push(parse_height(&c));
-                if c == 'S' {
-                    assert!(start_pos.is_none());
-                    start_pos = Some((i as u8, map.len() as u8))
-                } else if c == 'E' {
-                    assert!(end_pos.is_none());
-                    end_pos = Some((i as u8, map.len() as u8))
+        let mut start_pos: Option<GridPos> = None;
+        let mut end_pos: Option<GridPos> = None;
+
+        let map = Grid::parse_with(s, |pos, c| {
+            let marker = match c {
+                'S' => Some((&mut start_pos, "start")),
+                'E' => Some((&mut end_pos, "end")),
+                _ => None,
+            };
+
+            if let Some((marker_pos, name)) = marker {
+                if marker_pos.is_some() {
+                    return Err(Error::parse(format!("duplicate {} position", name)));
                 }
+                *marker_pos = Some(pos.clone());
             }
 
-            map.push(row);
-        }
-
-        let height = map.len() as u8;
+            parse_height(&c)
+        })?;
 
         Ok(HeightMap {
             map,
-            width: width.unwrap(),
-            height,
-            start_pos: start_pos.unwrap(),
-            end_pos: end_pos.unwrap(),
+            start_pos: start_pos.ok_or_else(|| Error::parse("missing start position"))?,
+            end_pos: end_pos.ok_or_else(|| Error::parse("missing end position"))?,
         })
     }
 }
 
 impl Display for HeightMap {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for (y, row) in self.map.iter().enumerate() {
-            for (x, height) in row.iter().enumerate() {
-                let c = match height {
-                    0 => {
-                        let pos = (x as u8, y as u8);
-                        if self.start_pos == pos {
-                            'S'
-                        } else if self.end_pos == pos {
-                            'E'
-                        } else {
-                            panic!();
-                        }
-                    }
-                    i => ((i - 1) + 'a' as u8) as char
-                };
-                write!(f, "{}", c)?;
+        for (pos, height) in self.map.iter() {
+            let c = if pos == self.start_pos {
+                'S'
+            } else if pos == self.end_pos {
+                'E'
+            } else {
+                ((height - 1) + 'a' as u8) as char
+            };
+            write!(f, "{}", c)?;
+
+            if pos.0 + 1 == self.map.width() {
+                writeln!(f)?;
             }
-            write!(f, "\n")?;
         }
         Ok(())
     }
 }
 
 impl HeightMap {
-    pub fn filtered_bfs<F: Fn(&u8, &u8) -> bool>(&self, filter: F) -> Bfs<F, HeightMap> {
-        Bfs::new(self, filter)
+    pub fn filtered_bfs<F: Fn(&u8, &u8) -> bool>(&self, filter: F) -> Bfs<'_, PropertyFilter<F>, Grid<u8>> {
+        Bfs::new(&self.map, filter)
     }
 
-    pub fn surroundings(&self, pos: &Vec2) -> Vec<Vec2> {
-        let mut result = Vec::new();
+    pub fn get_at(&self, pos: &GridPos) -> Option<&u8> {
+        self.map.get(pos)
+    }
 
-        let (x, y) = pos;
+    pub fn get_end_pos(&self) -> &GridPos {
+        &self.end_pos
+    }
 
-        if *x > 0 {
-            result.push((x - 1, *y));
-        }
+    pub fn get_start_pos(&self) -> &GridPos {
+        &self.start_pos
+    }
 
-        if *x < self.width - 1 {
-            result.push((x + 1, *y));
-        }
+    pub fn shortest_path(&self) -> Option<Vec<GridPos>> {
+        self.filtered_bfs(can_climb)
+            .run_to(&self.start_pos, &self.end_pos)
+            .path_to(&self.end_pos)
+    }
 
-        if *y > 0 {
-            result.push((*x, y - 1));
+    // the map with every step of the path marked by the direction it is taken in
+    pub fn render_path(&self, path: &[GridPos]) -> String {
+        let mut rendered = self.map.map(|_| '.');
+
+        for step in path.windows(2) {
+            let (Vec2(cx, cy), Vec2(nx, ny)) = (&step[0], &step[1]);
+            rendered[&step[0]] = match (nx.cmp(cx), ny.cmp(cy)) {
+                (Ordering::Greater, _) => '>',
+                (Ordering::Less, _) => '<',
+                (_, Ordering::Greater) => 'v',
+                _ => '^',
+            };
         }
-
-        if *y < self.height - 1 {
-            result.push((*x, y + 1));
+        if let Some(last) = path.last() {
+            rendered[last] = 'E';
         }
 
-        result
+        rendered.to_string()
     }
 
-    pub fn get_at(&self, pos: &Vec2) -> &u8 {
-        let (x, y) = pos;
-        &self.map[*y as usize][*x as usize]
+    pub fn get_lowest_positions(&self) -> Vec<GridPos> {
+        self.map.iter()
+            .filter(|(_, height)| **height == 1)
+            .map(|(pos, _)| pos)
+            .collect()
     }
+}
 
-    pub fn get_end_pos(&self) -> &Vec2 {
-        &self.end_pos
-    }
+pub fn can_climb(cur: &u8, next: &u8) -> bool {
+    *next <= *cur || *next == cur+1
+}
 
-    pub fn get_start_pos(&self) -> &Vec2 {
-        &self.start_pos
-    }
+pub struct Day12;
 
-    pub fn get_lowest_positions(&self) -> Vec<Vec2> {
-        let mut result = Vec::new();
-        for (y, row) in self.map.iter().enumerate() {
-            for (x, c) in row.iter().enumerate() {
-                if *c == 1 {
-                    result.push((x as u8, y as u8));
-                }
-            }
-        }
+impl Solver for Day12 {
+    type Puzzle = HeightMap;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-        result
+    fn parse(input: &str) -> Result<Self::Puzzle, Error> {
+        input.parse()
     }
-}
 
-impl Graph for HeightMap {
-    type Position = Vec2;
-    type Property = u8;
-
-    fn get_property(&self, pos: &Self::Position) -> &Self::Property {
-        self.get_at(pos)
+    fn part1(height_map: &Self::Puzzle) -> Result<Self::Answer1, Error> {
+        height_map.filtered_bfs(can_climb)
+            .run_to(height_map.get_start_pos(), height_map.get_end_pos())
+            .dist(height_map.get_end_pos())
+            .ok_or_else(|| Error::invalid_state("end position is not reachable"))
     }
 
-    fn get_surroundings(&self, pos: &Self::Position) -> Vec<Self::Position> {
-        self.surroundings(pos)
-    }
-}
+    fn part2(height_map: &Self::Puzzle) -> Result<Self::Answer2, Error> {
+        let end_pos = height_map.get_end_pos();
 
-pub fn can_climb(cur: &u8, next: &u8) -> bool {
-    *next <= *cur || *next == cur+1
+        height_map.filtered_bfs(can_climb)
+            .run_from_until(&height_map.get_lowest_positions(), |pos| pos == end_pos)
+            .dist(end_pos)
+            .ok_or_else(|| Error::invalid_state("no lowest position can reach the end position"))
+    }
 }
 
 #[cfg(test)]
@@ -176,6 +170,12 @@ mod test {
         let hm: HeightMap = EXAMPLE.parse().unwrap();
 
         println!("{}", hm);
+        assert_eq!(EXAMPLE.trim_end(), hm.to_string().trim_end());
+        assert_eq!(Some(&1), hm.get_at(&Vec2(0, 0)));
+
+        let error = "Sab\nEcS".parse::<HeightMap>().unwrap_err();
+        assert_eq!("line 2, column 3: duplicate start position", error.to_string());
+        assert!("Sab".parse::<HeightMap>().is_err());
     }
 
     #[test]
@@ -185,31 +185,49 @@ mod test {
         let bfs = hm.filtered_bfs(can_climb);
         let dists = bfs.run(&hm.start_pos);
 
-        for row in 0..hm.height {
-            for h in 0..hm.width {
-                print!("{:02}  ", dists[&(h, row)]);
+        for row in 0..hm.map.height() {
+            for h in 0..hm.map.width() {
+                print!("{:02}  ", dists[&Vec2(h, row)]);
             }
             println!();
         }
 
-        let (ex, ey) = &hm.end_pos;
-
-        let steps = dists[&(*ex, *ey)];
+        let steps = dists[&hm.end_pos];
         println!("{}", steps);
         assert_eq!(31, steps);
     }
 
     #[test]
-    fn test_ms_bfs() {
+    fn test_shortest_path() {
         let hm: HeightMap = EXAMPLE.parse().unwrap();
-        let end_pos = &hm.get_end_pos();
-        
-        let bfs = hm.filtered_bfs(|c, n| *c <= *n || *c == n+1);
-        let dists = bfs.run(&end_pos);
-        
-        let lowest = *hm.get_lowest_positions().iter().filter_map(|(lx, ly)| dists.get(&(*lx, *ly))).min().unwrap();
 
-        println!("{}", lowest);
-        assert_eq!(29, lowest);
+        let path = hm.shortest_path().unwrap();
+        println!("{}", hm.render_path(&path));
+
+        assert_eq!(32, path.len());
+        assert_eq!(Some(&hm.start_pos), path.first());
+        assert_eq!(Some(&hm.end_pos), path.last());
+        assert!(path.windows(2).all(|s| can_climb(&hm.map[&s[0]], &hm.map[&s[1]])));
+        assert_eq!(">>vv<<<<\n..vvv<<^\n..vv>E^^\n..v>>>^^\n..>>>>>^\n", hm.render_path(&path));
     }
+
+    #[test]
+    fn test_ms_bfs() {
+        let hm: HeightMap = EXAMPLE.parse().unwrap();
+        let end_pos = hm.get_end_pos();
+        let lowest_positions = hm.get_lowest_positions();
+
+        let result = hm.filtered_bfs(can_climb).run_from(&lowest_positions);
+        let source = result.source(end_pos).unwrap();
+        println!("{:?}", result.path_to(end_pos));
+        assert_eq!(Some(29), result.dist(end_pos));
+        assert!(lowest_positions.contains(source));
+
+        let reversed = hm.filtered_bfs(can_climb).reversed()
+            .run_until(end_pos, |p| hm.get_at(p) == Some(&1));
+        let reached = reversed.reached().unwrap();
+        assert_eq!(Some(29), reversed.dist(reached));
+        assert_eq!(Some(end_pos), reversed.source(reached));
+    }
+
 }
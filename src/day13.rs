@@ -4,6 +4,8 @@ use std::iter::zip;
 use std::mem::replace;
 use std::str::FromStr;
 use crate::Error;
+use crate::solver::Solver;
+use crate::utils::parse_at;
 
 #[derive(Debug, Clone)]
 pub enum Element {
@@ -46,17 +48,17 @@ impl PartialEq<Self> for Element {
     }
 }
 
-impl Ord for Element {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+impl PartialOrd for Element {
+    fn partial_cmp(&self, other: &Element) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-impl PartialOrd for Element {
-    fn partial_cmp(&self, other: &Element) -> Option<Ordering> {
+impl Ord for Element {
+    fn cmp(&self, other: &Element) -> Ordering {
         use Element::*;
 
-        let result = match (self, other) {
+        match (self, other) {
             (Value(v1), Value(v2)) => v1.cmp(v2),
             (Value(v1), List(_)) => Element::wrap(*v1).cmp(other),
             (List(_), Value(v2)) => self.cmp(&Element::wrap(*v2)),
@@ -64,15 +66,13 @@ impl PartialOrd for Element {
                 for (e1, e2) in zip(l1, l2) {
                     let ordering = e1.cmp(e2);
                     if ordering != Ordering::Equal {
-                        return Some(ordering);
+                        return ordering;
                     }
                 }
 
                 l1.len().cmp(&l2.len())
             }
-        };
-
-        Some(result)
+        }
     }
 }
 
@@ -82,18 +82,17 @@ impl FromStr for Element {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut list_stack: Vec<Vec<Element>> = Vec::new();
 
-        let mut accu_string = String::new();
+        let mut accu_start: Option<usize> = None;
 
-        for c in s.chars() {
+        for (i, c) in s.char_indices() {
 
             match c {
                 '[' => list_stack.push(Vec::new()),
                 ']' => {
-                    let mut last_list = list_stack.pop().ok_or(Error(format!("invalid nesting for: {} (on ])", s)))?;
-                    if !accu_string.is_empty() {
-                        let v: u32 = accu_string.parse()?;
+                    let mut last_list = list_stack.pop().ok_or_else(|| Error::parse(format!("invalid nesting for: {} (on ])", s)).with_column(i + 1))?;
+                    if let Some(start) = accu_start.take() {
+                        let v: u32 = parse_at(s, &s[start..i])?;
                         last_list.push(Element::Value(v));
-                        accu_string.clear();
                     }
                     match list_stack.last_mut() {
                         None => return Ok(Element::List(last_list)),
@@ -101,36 +100,36 @@ impl FromStr for Element {
                     }
                 },
                 ',' => {
-                    let last_list = list_stack.last_mut().ok_or(Error(format!("invalid nesting for: {} (on ,)", s)))?;
+                    let last_list = list_stack.last_mut().ok_or_else(|| Error::parse(format!("invalid nesting for: {} (on ,)", s)).with_column(i + 1))?;
 
-                    if !accu_string.is_empty() {
-                        let v: u32 = accu_string.parse()?;
+                    if let Some(start) = accu_start.take() {
+                        let v: u32 = parse_at(s, &s[start..i])?;
                         last_list.push(Element::Value(v));
-                        accu_string.clear();
                     }
                 },
-                v => {
-                    accu_string.push(v);
+                _ => {
+                    accu_start.get_or_insert(i);
                 }
             }
 
         }
 
-        Err(Error(format!("invalid string: {}", s)))
+        Err(Error::parse(format!("invalid string: {}", s)))
     }
 }
 
 #[derive(Debug)]
 pub struct ListPair(pub Element, pub Element);
 
-impl FromIterator<Element> for ListPair {
-    fn from_iter<T: IntoIterator<Item=Element>>(iter: T) -> Self {
-        let mut i = iter.into_iter();
-        let item1 = i.next().unwrap();
-        let item2 = i.next().unwrap();
-        assert!(i.next().is_none());
+impl TryFrom<Vec<Element>> for ListPair {
+    type Error = Error;
+
+    fn try_from(elements: Vec<Element>) -> Result<Self, Self::Error> {
+        let count = elements.len();
+        let [item1, item2]: [Element; 2] = elements.try_into()
+            .map_err(|_| Error::parse(format!("expected a pair of lists, got {} lists", count)))?;
 
-        ListPair(item1, item2)
+        Ok(ListPair(item1, item2))
     }
 }
 
@@ -152,21 +151,65 @@ impl FromStr for AllListPairs {
         let mut lists = Vec::new();
         let mut collector = Vec::new();
 
-        for line in s.lines().map(|v| v.trim_end()) {
+        for (i, line) in s.lines().map(|v| v.trim_end()).enumerate() {
             if line.is_empty() {
                 let cur_col = replace(&mut collector, Vec::new());
-                let list_pair = ListPair::from_iter(cur_col.into_iter());
+                let list_pair = ListPair::try_from(cur_col).map_err(|e| e.with_line(i + 1))?;
                 lists.push(list_pair);
                 continue;
             }
-            let element: Element = line.parse()?;
+            let element: Element = line.parse().map_err(|e: Error| e.with_line(i + 1))?;
             collector.push(element);
         }
 
+        if !collector.is_empty() {
+            lists.push(ListPair::try_from(collector).map_err(|e| e.with_line(s.lines().count()))?);
+        }
+
         Ok(AllListPairs(lists))
     }
 }
 
+pub struct Day13;
+
+impl Solver for Day13 {
+    type Puzzle = AllListPairs;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Puzzle, Error> {
+        input.parse()
+    }
+
+    fn part1(pairs: &Self::Puzzle) -> Result<Self::Answer1, Error> {
+        let AllListPairs(pairs) = pairs;
+
+        Ok(pairs.iter().enumerate()
+            .filter(|(_, e)| e.is_in_right_order())
+            .map(|(i, _)| i + 1)
+            .sum())
+    }
+
+    fn part2(pairs: &Self::Puzzle) -> Result<Self::Answer2, Error> {
+        let AllListPairs(pairs) = pairs;
+
+        let mut elements: Vec<&Element> = pairs.iter().flat_map(|ListPair(e1, e2)| [e1, e2]).collect();
+
+        let div1 = Element::List(vec![Element::wrap(2)]);
+        let div2 = Element::List(vec![Element::wrap(6)]);
+
+        elements.push(&div1);
+        elements.push(&div2);
+
+        elements.sort();
+
+        Ok(elements.iter().enumerate()
+            .filter(|(_, e)| **e == &div1 || **e == &div2)
+            .map(|(i, _)| i + 1)
+            .product())
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -227,6 +270,8 @@ mod test {
 
         let code: usize = pairs.iter().enumerate().filter(|(_,e)| e.is_in_right_order()).map(|(i, _)| i+1).sum();
         println!("code: {}", code);
+        assert!("[1]\n[2]\n[3]\n".parse::<AllListPairs>().is_err());
+        assert!("[1]\n\n[2]\n[3]\n".parse::<AllListPairs>().is_err());
     }
 
 }
\ No newline at end of file
@@ -1,41 +1,26 @@
-use std::cmp::{max, min};
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 use crate::day14::WorldElement::*;
 use crate::Error;
+use crate::utils::parse_lines;
+use crate::utils::bounding_box::BoundingBox;
+use crate::utils::grid::Grid;
+use crate::solver::Solver;
 pub use crate::utils::vec2::Vector2;
 pub use crate::utils::vec2::Vec2;
 
-pub type Position = u32;
+pub type Position = usize;
 
 pub type PosVec = Vec2<Position>;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Line(PosVec, PosVec);
 
-#[derive(Default, Debug, Clone, PartialEq, Eq)]
-pub struct Box(PosVec, PosVec);
+impl Line {
 
-impl Box {
-
-    pub fn new() -> Box {
-        Box::default()
-    }
-
-    pub fn new_with_dimension(top_left: PosVec, bottom_right: PosVec) -> Box {
-        Box(top_left, bottom_right)
-    }
-
-    pub fn new_with_size(top_left: PosVec, size: PosVec) -> Box {
-        let Vec2(x, y) = top_left;
-        let Vec2(sx, sy) = size;
-
-        Box(Vec2(x, y), Vec2(x + sx, y + sy))
-    }
-
-    pub fn size(&self) -> PosVec {
-        let Box(Vec2(sx, sy), Vec2(ex, ey)) = self;
-        Vec2(ex - sx, ey - sy)
+    pub fn bounds(&self) -> BoundingBox<PosVec> {
+        let Line(start, end) = self;
+        BoundingBox::new(start.clone(), end.clone())
     }
 
 }
@@ -55,8 +40,8 @@ impl FromStr for LineRow {
         while let Some(cur_coor) = cur {
             let next = split.next();
             if let Some(next_coor) = next {
-                let cur_coor: PosVec = cur_coor.parse()?;
-                let next_coor: PosVec = next_coor.parse()?;
+                let cur_coor: PosVec = cur_coor.parse().map_err(|e: Error| e.at(s, cur_coor))?;
+                let next_coor: PosVec = next_coor.parse().map_err(|e: Error| e.at(s, next_coor))?;
                 result.push(Line(cur_coor, next_coor));
             }
             cur = next;
@@ -66,102 +51,23 @@ impl FromStr for LineRow {
     }
 }
 
-pub trait BoundingBox {
-    fn get_bounds(&self) -> Box;
-
-    fn get_top_left(&self) -> PosVec {
-        self.get_bounds().get_top_left()
-    }
-
-    fn get_top_right(&self) -> PosVec {
-        self.get_bounds().get_top_right()
-    }
+impl LineRow {
 
-    fn get_bottom_left(&self) -> PosVec {
-        self.get_bounds().get_bottom_left()
-    }
-
-    fn get_bottom_right(&self) -> PosVec {
-        self.get_bounds().get_bottom_right()
-    }
-}
-
-impl BoundingBox for PosVec {
-    fn get_bounds(&self) -> Box {
-        Box(self.clone(), self.clone())
-    }
-}
-
-impl BoundingBox for Line {
-    fn get_bounds(&self) -> Box {
-        let Line(l1, l2) = self;
-        let Vec2(x1, y1) = l1;
-        let Vec2(x2, y2) = l2;
-
-        let min_vec = Vec2(min(*x1, *x2), min(*y1, *y2));
-        let max_vec = Vec2(max(*x1, *x2), max(*y1, *y2));
-
-        Box(min_vec, max_vec)
-    }
-}
-
-impl BoundingBox for Box {
-    fn get_bounds(&self) -> Box {
-        self.clone()
-    }
-
-    fn get_top_left(&self) -> PosVec {
-        self.0.clone()
-    }
-
-    fn get_top_right(&self) -> PosVec {
-        let Box(Vec2(_, sy), Vec2(ex, _)) = self;
-
-        Vec2(*ex, *sy)
-    }
-
-    fn get_bottom_left(&self) -> PosVec {
-        let Box(Vec2(sx, _), Vec2(_, ey)) = self;
-
-        Vec2(*sx, *ey)
-    }
-
-    fn get_bottom_right(&self) -> PosVec {
-        self.1.clone()
+    pub fn bounds(&self) -> Option<BoundingBox<PosVec>> {
+        let LineRow(lines) = self;
+        lines.iter()
+            .map(Line::bounds)
+            .reduce(|acc, bounds| acc.union(&bounds))
     }
 
 }
 
-impl<'a, B: 'a + BoundingBox> FromIterator<&'a B> for Box {
-    fn from_iter<T: IntoIterator<Item = &'a B>>(iter: T) -> Self {
-        let mut min_x = Position::MAX;
-        let mut min_y = Position::MAX;
-        let mut max_x = Position::MIN;
-        let mut max_y = Position::MIN;
-
-        for item in iter {
-            let bounding_box = item;
-            let Box(Vec2(x1, y1), Vec2(x2, y2)) = bounding_box.get_bounds();
-            let local_min_x = min(x1, x2);
-            let local_min_y = min(y1, y2);
-            let local_max_x = max(x1, x2);
-            let local_max_y = max(y1, y2);
-        
-            min_x = min(min_x, local_min_x);
-            min_y = min(min_y, local_min_y);
-            max_x = max(max_x, local_max_x);
-            max_y = max(max_y, local_max_y);
-        }
-        
-        Box(Vec2(min_x, min_y), Vec2(max_x, max_y))
-    }
-}
-
-impl BoundingBox for LineRow {
-    fn get_bounds(&self) -> Box {
-        let LineRow(lines) = self;
-        lines.iter().collect()
-    }
+// the bounds of all walls
+pub fn wall_bounds(rows: &[LineRow]) -> Result<BoundingBox<PosVec>, Error> {
+    rows.iter()
+        .filter_map(LineRow::bounds)
+        .reduce(|acc, bounds| acc.union(&bounds))
+        .ok_or_else(|| Error::invalid_state("no walls found"))
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -185,113 +91,82 @@ impl Display for WorldElement {
 
 #[derive(Debug)]
 pub struct World {
-    size: PosVec,
-    buffer: Vec<WorldElement>,
+    grid: Grid<WorldElement>,
     insert_pos: PosVec,
 }
 
 impl World {
 
     pub fn new(size: PosVec, insert_pos: PosVec) -> World {
-        let Vec2(width, height) = &size;
-        let buffer =vec![Nothing; *width as usize * *height as usize];
+        let Vec2(width, height) = size;
 
         World {
-            size,
-            buffer,
+            grid: Grid::filled(width, height, Nothing),
             insert_pos,
         }
     }
 
-    pub fn insert_lines(&mut self, line_defs: &Vec<LineRow>) {
+    pub fn insert_lines(&mut self, line_defs: &Vec<LineRow>) -> Result<(), Error> {
         for line_def in line_defs {
             let LineRow(lines) = line_def;
             for line in lines {
-                self.insert_wall(line);
+                self.insert_wall(line)?;
             }
         }
+        Ok(())
     }
 
-    fn insert_wall(&mut self, wall: &Line) {
-        let Line(Vec2(sx, sy), Vec2(ex, ey)) = wall;
-
-        let asx = min(sx, ex);
-        let aex = max(sx, ex);
-        let asy = min(sy, ey);
-        let aey = max(sy, ey);
-
-        for x in *asx..=*aex {
-            for y in *asy..=*aey {
-                let wall_pos = Vec2(x, y);
-                self.insert_wall_at(&wall_pos);
-            }
+    fn insert_wall(&mut self, wall: &Line) -> Result<(), Error> {
+        for wall_pos in wall.bounds().points() {
+            self.insert_wall_at(&wall_pos)?;
         }
+        Ok(())
     }
 
-    fn insert_wall_at(&mut self, pos: &PosVec) {
-        self.insert_at(pos, Wall);
-    }
-
-    fn insert_sand_at(&mut self, pos: &PosVec) {
-        self.insert_at(pos, FixedSand);
+    fn insert_wall_at(&mut self, pos: &PosVec) -> Result<(), Error> {
+        self.insert_at(pos, Wall)
     }
 
-    fn insert_at(&mut self, pos: &PosVec, element: WorldElement) {
-        let buffer_pos = self.vec2_to_buffer_pos(pos);
-        self.buffer[buffer_pos] = element;
+    fn insert_sand_at(&mut self, pos: &PosVec) -> Result<(), Error> {
+        self.insert_at(pos, FixedSand)
     }
 
-    fn vec2_to_buffer_pos(&self, pos: &PosVec) -> usize {
-        let Vec2(x, y) = pos;
-        let Vec2(width, height) = &self.size;
-
-        assert!(x < width);
-        assert!(y < height);
-
-        (*y * width + *x) as usize
+    fn insert_at(&mut self, pos: &PosVec, element: WorldElement) -> Result<(), Error> {
+        let cell = self.grid.get_mut(pos)
+            .ok_or_else(|| Error::invalid_state(format!("position out of bounds: {:?}", pos)))?;
+        *cell = element;
+        Ok(())
     }
 
     pub fn is_in_bounds(&self, pos: &PosVec) -> bool {
-        let Vec2(x, y) = pos;
-        let Vec2(width, height) = &self.size;
-
-        x < width && y < height
+        self.grid.contains(pos)
     }
 
-    pub fn view_port(&self) -> ViewPort {
-        self.view_port_at(&Box::new_with_dimension(Vec2(494, 0), Vec2(503, 9)))
+    pub fn view_port(&self) -> ViewPort<'_> {
+        self.view_port_at(&BoundingBox::new(Vec2(494, 0), Vec2(503, 9)))
     }
 
-    pub fn view_port_at(&self, view_port: &Box) -> ViewPort {
+    pub fn view_port_at(&self, view_port: &BoundingBox<PosVec>) -> ViewPort<'_> {
         ViewPort {
             world: self,
             view_port: view_port.clone(),
         }
     }
 
-    pub fn get_element_at(&self, pos: &PosVec) -> &WorldElement {
-        let buffer_pos = self.vec2_to_buffer_pos(&pos);
-        &self.buffer[buffer_pos]
-    }
-
     pub fn try_get_element_at(&self, pos: &PosVec) -> Option<&WorldElement> {
-        if !self.is_in_bounds(&pos) {
-            return None;
-        }
-
-        Some(self.get_element_at(pos))
+        self.grid.get(pos)
     }
 
-    pub fn drop_sand(&mut self) -> PosVec {
+    pub fn drop_sand(&mut self) -> Result<PosVec, Error> {
         let mut cur_pos = self.insert_pos.clone();
 
         while let Some(new_pos) = self.find_next_position(&cur_pos) {
             cur_pos = new_pos;
         }
 
-        self.insert_sand_at(&cur_pos);
+        self.insert_sand_at(&cur_pos)?;
 
-        cur_pos
+        Ok(cur_pos)
     }
 
     pub fn find_next_position(&self, pos: &PosVec) -> Option<PosVec> {
@@ -300,9 +175,11 @@ impl World {
         if self.try_get_element_at(&try_position) == Some(&Nothing) {
             return Some(try_position);
         }
-        let try_position = Vec2(x-1, y+1);
-        if self.try_get_element_at(&try_position) == Some(&Nothing) {
-            return Some(try_position);
+        if let Some(left_x) = x.checked_sub(1) {
+            let try_position = Vec2(left_x, y+1);
+            if self.try_get_element_at(&try_position) == Some(&Nothing) {
+                return Some(try_position);
+            }
         }
         let try_position = Vec2(x+1, y+1);
         if self.try_get_element_at(&try_position) == Some(&Nothing) {
@@ -316,15 +193,13 @@ impl World {
 #[derive(Debug)]
 pub struct ViewPort<'a> {
     world: &'a World,
-    view_port: Box,
+    view_port: BoundingBox<PosVec>,
 }
 
 impl<'a> Display for ViewPort<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let Box(Vec2(sx, sy), Vec2(ex, ey)) = &self.view_port;
-
-        for y in *sy..=*ey {
-            for x in *sx..=*ex {
+        for y in self.view_port.y_range() {
+            for x in self.view_port.x_range() {
                 let cur = Vec2(x, y);
                 let element = self.world.try_get_element_at(&cur).unwrap_or(&Nothing);
                 write!(f, "{}", element)?;
@@ -337,6 +212,57 @@ impl<'a> Display for ViewPort<'a> {
 }
 
 
+pub struct Day14;
+
+impl Solver for Day14 {
+    type Puzzle = Vec<LineRow>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Puzzle, Error> {
+        parse_lines(input)
+    }
+
+    fn part1(rows: &Self::Puzzle) -> Result<Self::Answer1, Error> {
+        let Vec2(bx, by) = wall_bounds(rows)?.bottom_right().clone();
+
+        let mut world = World::new(Vec2(bx + 1, by + 1), Vec2(500, 0));
+        world.insert_lines(rows)?;
+
+        let mut counter: usize = 0;
+
+        loop {
+            let end_pos = world.drop_sand()?;
+            if end_pos.get_y() >= &by {
+                break;
+            }
+            counter += 1;
+        }
+
+        Ok(counter)
+    }
+
+    fn part2(rows: &Self::Puzzle) -> Result<Self::Answer2, Error> {
+        let Vec2(bx, by) = wall_bounds(rows)?.bottom_right().clone();
+
+        let insert_pos = Vec2(500, 0);
+        let mut world = World::new(Vec2(bx * 2, by + 2), insert_pos.clone());
+        world.insert_lines(rows)?;
+
+        let mut counter: usize = 0;
+
+        loop {
+            counter += 1;
+            let end_pos = world.drop_sand()?;
+            if end_pos == insert_pos {
+                break;
+            }
+        }
+
+        Ok(counter)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::utils::vec2::Vector2;
@@ -350,31 +276,34 @@ mod test {
         let rows = rows.unwrap();
         println!("{:#?}", rows);
 
-        let bounding_box: Box = rows.iter().collect();
+        let bounding_box = wall_bounds(&rows).unwrap();
         println!("{:?}", bounding_box);
+        assert_eq!(BoundingBox::new(Vec2(494, 4), Vec2(503, 9)), bounding_box);
 
         let mut world = World::new(Vec2(504, 10), Vec2(500, 0));
-        world.insert_lines(&rows);
+        world.insert_lines(&rows).unwrap();
         let view_port = world.view_port();
         println!("{}", view_port);
+        let mut world = World::new(Vec2(2, 2), Vec2(5, 0));
+        assert!(world.drop_sand().is_err());
+        assert!(world.insert_lines(&rows).is_err());
     }
 
     #[test]
     fn test_drop() {
         let rows: Result<Vec<LineRow>, Error> = EXAMPLE.lines().map(str::trim_end).map(|l| l.parse::<LineRow>()).collect();
         let rows = rows.unwrap();
-        let bounding_box: Box = rows.iter().collect();
-        let Vec2(bx, by) = bounding_box.get_bottom_right();
+        let Vec2(bx, by) = wall_bounds(&rows).unwrap().bottom_right().clone();
         let stop_line = by;
 
         let mut world = World::new(Vec2(bx+1, by+1), Vec2(500, 0));
-        world.insert_lines(&rows);
+        world.insert_lines(&rows).unwrap();
         println!("{}", world.view_port());
 
         let mut counter = 0;
 
         loop {
-            let end_pos = world.drop_sand();
+            let end_pos = world.drop_sand().unwrap();
             if end_pos.get_y() >= &stop_line {
                 break;
             }
@@ -389,20 +318,19 @@ mod test {
     fn test_drop_part2() {
         let rows: Result<Vec<LineRow>, Error> = EXAMPLE.lines().map(str::trim_end).map(|l| l.parse::<LineRow>()).collect();
         let rows = rows.unwrap();
-        let bounding_box: Box = rows.iter().collect();
-        let Vec2(bx, by) = bounding_box.get_bottom_right();
+        let Vec2(bx, by) = wall_bounds(&rows).unwrap().bottom_right().clone();
 
         let insert_pos = Vec2(500, 0);
         let mut world = World::new(Vec2(bx+20, by+2), insert_pos.clone());
-        world.insert_lines(&rows);
-        let dimension = Box::new_with_dimension(Vec2(450, 0), Vec2(550, 15));
+        world.insert_lines(&rows).unwrap();
+        let dimension = BoundingBox::new(Vec2(450, 0), Vec2(550, 15));
         println!("{}", world.view_port_at(&dimension));
 
         let mut counter = 0;
 
         loop {
             counter+=1;
-            let end_pos = world.drop_sand();
+            let end_pos = world.drop_sand().unwrap();
             if end_pos == insert_pos {
                 break;
             }
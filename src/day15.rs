@@ -4,8 +4,11 @@ use std::ops::RangeInclusive;
 use std::sync::{Arc, RwLock};
 use std::thread;
 use crate::{Error, Scored};
+use crate::solver::Solver;
+use crate::utils::parse_lines;
+use crate::utils::parser::parse_with;
 
-use crate::utils::ranges::{RangeExt, RangeLength};
+use crate::utils::ranges::{RangeLength, RangeSet};
 
 pub use crate::utils::vec2::Vector2;
 pub use crate::utils::vec2::Vec2;
@@ -34,97 +37,24 @@ impl FromStr for SensorBeacon {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let split: Vec<&str> = s.split(&[' ', '=', ',', ':'][..]).collect();
-
-        match split[..] {
-            ["Sensor", "at", "x", sx, "", "y", sy, "", "closest", "beacon", "is", "at", "x", bx, "", "y", by] => {
-                let sx: Pos = sx.trim().parse().map_err(|_| Error(format!("cannot parse {}", sx)))?;
-                let sy: Pos = sy.trim().parse().map_err(|_| Error(format!("cannot parse {}", sy)))?;
-                let bx: Pos = bx.trim().parse().map_err(|_| Error(format!("cannot parse {}", bx)))?;
-                let by: Pos = by.trim().parse().map_err(|_| Error(format!("cannot parse {}", by)))?;
-                Ok(SensorBeacon(Vec2(sx, sy), Vec2(bx, by)))
-            }
-            _ => Err(Error(format!("invalid line: {:?}", split)))
-        }
+        parse_with(s, |p| {
+            p.literal("Sensor at x=")?;
+            let sx = p.integer()?;
+            p.literal(", y=")?;
+            let sy = p.integer()?;
+            p.literal(": closest beacon is at x=")?;
+            let bx = p.integer()?;
+            p.literal(", y=")?;
+            let by = p.integer()?;
+
+            Ok(SensorBeacon(Vec2(sx, sy), Vec2(bx, by)))
+        })
     }
 }
 
-#[derive(Debug, Default)]
-pub struct RangeCollector {
-    ranges: Vec<RangeInclusive<Pos>>,
-}
-
-impl RangeCollector {
-    fn add_range(&mut self, add_range: RangeInclusive<Pos>) {
-        let mut add_ranges = vec![add_range];
-
-        for range in self.ranges.iter() {
-            let mut new_ranges = Vec::new();
-
-            for add_range in add_ranges.drain(..) {
-                let split_off = add_range.split_off(range);
-
-                if let Some(r1) = &split_off[0] {
-                    new_ranges.push(r1.clone());
-                }
-
-                if let Some(r2) = &split_off[2] {
-                    new_ranges.push(r2.clone());
-                }
-            }
-
-
-            add_ranges = new_ranges;
-        }
-
-        for range in add_ranges {
-            self.ranges.push(range);
-        }
-    }
-
-    pub fn ranges(self) -> Vec<RangeInclusive<Pos>> {
-        self.ranges
-    }
-
-    pub fn contains(&self, value: &Pos) -> bool {
-        self.ranges.iter().any(|r| r.contains(value))
-    }
-
-    pub fn find_first_gap(&self) -> Option<Pos> {
-        if self.ranges.is_empty() {
-            return None;
-        }
-
-        let mut sorted_ranges = self.ranges.clone();
-        sorted_ranges.sort_by_key(|r| *r.start());
-
-        let mut iter = sorted_ranges.into_iter();
-        let mut last_range = iter.next().unwrap();
-
-        for range in iter {
-            if last_range.end() + 1 != *range.start() {
-                return Some(last_range.end() + 1);
-            }
-            last_range = range;
-        }
-
-        None
-    }
-
-    pub fn find_gaps(&self, search_range: RangeInclusive<Pos>) -> Vec<Pos> {
-        search_range.into_iter().filter(|r| !self.contains(r)).collect()
-    }
-
-}
-
-impl FromIterator<RangeInclusive<Pos>> for RangeCollector {
-    fn from_iter<T: IntoIterator<Item=RangeInclusive<Pos>>>(iter: T) -> Self {
-        let mut range_collector = RangeCollector::default();
-        for item in iter {
-            range_collector.add_range(item);
-        }
-        range_collector
-    }
+fn find_first_gap(ranges: &RangeSet<Pos>) -> Option<Pos> {
+    let span = ranges.span()?;
+    ranges.gaps(span).next().map(|gap| *gap.start())
 }
 
 pub struct BeaconFinder<'a> {
@@ -132,14 +62,14 @@ pub struct BeaconFinder<'a> {
 }
 
 impl<'a> BeaconFinder<'a> {
-    pub fn new(sensor_beacons: &'a Vec<SensorBeacon>) -> BeaconFinder {
+    pub fn new(sensor_beacons: &'a Vec<SensorBeacon>) -> BeaconFinder<'a> {
         BeaconFinder {
             sensor_beacons
         }
     }
 
-    pub fn find_impossible_locations(&self, row: &Pos) -> RangeCollector {
-        let mut range_collector = RangeCollector::default();
+    pub fn find_impossible_locations(&self, row: &Pos) -> RangeSet<Pos> {
+        let mut range_set = RangeSet::new();
 
         for sb in self.sensor_beacons {
             let SensorBeacon(sensor, _) = sb;
@@ -154,10 +84,10 @@ impl<'a> BeaconFinder<'a> {
             let remaining_dist = dist - dist_to_row;
 
             let range = sensor.get_x() - remaining_dist..=sensor.get_x() + remaining_dist;
-            range_collector.add_range(range);
+            range_set.insert(range);
         }
 
-        range_collector
+        range_set
     }
 
     pub fn find_beacons_on_row(&self, row: &Pos) -> usize {
@@ -170,17 +100,16 @@ impl<'a> BeaconFinder<'a> {
     }
 
     pub fn find_impossible_beacon(&self, row: &Pos) -> usize {
-        let ranges = self.find_impossible_locations(row).ranges();
+        let ranges = self.find_impossible_locations(row);
         let beacons_on_row = self.find_beacons_on_row(&row);
 
-        let len: usize = ranges.iter().map(|r| r.len() as usize).sum();
-        len - beacons_on_row
+        ranges.len() as usize - beacons_on_row
     }
 
     pub fn find_beacon_location_in_range(&self, range: RangeInclusive<Pos>) -> Option<PosVec> {
         for row in range {
-            let range_collector = self.find_impossible_locations(&row);
-            if let Some(first_gap) = range_collector.find_first_gap() {
+            let ranges = self.find_impossible_locations(&row);
+            if let Some(first_gap) = find_first_gap(&ranges) {
                 return Some(Vec2(first_gap, row))
             }
         }
@@ -188,12 +117,12 @@ impl<'a> BeaconFinder<'a> {
         None
     }
 
-    pub fn find_beacon_location_threaded<const N_THREADS: Pos>(&self, max_row: Pos) -> PosVec {
+    pub fn find_beacon_location_threaded<const N_THREADS: Pos>(&self, max_row: Pos) -> Result<PosVec, Error> {
         let batch_size = max_row / N_THREADS;
 
         let target: Arc<RwLock<Option<PosVec>>> = Arc::new(RwLock::new(None));
 
-        thread::scope(|scope| {
+        thread::scope(|scope| -> Result<(), Error> {
 
             let mut join_handles = Vec::new();
 
@@ -206,13 +135,15 @@ impl<'a> BeaconFinder<'a> {
                 let search_range = start_batch as Pos..=end_batch as Pos;
                 let thread = scope.spawn(move|| {
                     for row in search_range {
-                        if target_clone.read().unwrap().is_some() {
+                        if target_clone.read().map_or(true, |target| target.is_some()) {
                             break;
                         }
 
-                        let range_collector = self.find_impossible_locations(&row);
-                        if let Some(first_gap) = range_collector.find_first_gap() {
-                            *target_clone.write().unwrap() = Some(Vec2(first_gap, row))
+                        let ranges = self.find_impossible_locations(&row);
+                        if let Some(first_gap) = find_first_gap(&ranges) {
+                            if let Ok(mut target) = target_clone.write() {
+                                *target = Some(Vec2(first_gap, row));
+                            }
                         }
                     }
                 });
@@ -220,12 +151,73 @@ impl<'a> BeaconFinder<'a> {
             }
 
             for handle in join_handles {
-                handle.join().unwrap();
+                handle.join().map_err(|_| Error::invalid_state("search thread panicked"))?;
             }
-        });
 
-        let locked = target.read().unwrap();
-        locked.as_ref().unwrap().clone()
+            Ok(())
+        })?;
+
+        let locked = target.read().map_err(|_| Error::invalid_state("search result is poisoned"))?;
+        locked.clone().ok_or_else(|| Error::invalid_state("no beacon location found"))
+    }
+}
+
+#[derive(Debug)]
+pub struct Scan {
+    pub sensor_beacons: Vec<SensorBeacon>,
+    pub row: Pos,
+    pub max_row: Pos,
+}
+
+// the puzzle only states the search area in its text, it is 0..=20 for the example and 0..=4_000_000
+// for real inputs, with the query row in the middle. the sensors are spread over that area, so rounding
+// their largest coordinate up to a single significant digit recovers it
+fn search_area(sensor_beacons: &[SensorBeacon]) -> Result<Pos, Error> {
+    let extent = sensor_beacons.iter()
+        .map(|SensorBeacon(sensor, _)| *sensor.get_x().max(sensor.get_y()))
+        .max()
+        .ok_or_else(|| Error::invalid_state("no sensors found"))?
+        .max(0);
+
+    let mut scale = 1;
+    while extent / scale >= 10 {
+        scale *= 10;
+    }
+
+    Ok((extent + scale - 1) / scale * scale)
+}
+
+impl FromStr for Scan {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let sensor_beacons: Vec<SensorBeacon> = parse_lines(s)?;
+        let max_row = search_area(&sensor_beacons)?;
+
+        Ok(Scan { sensor_beacons, row: max_row / 2, max_row })
+    }
+}
+
+pub struct Day15;
+
+impl Solver for Day15 {
+    type Puzzle = Scan;
+    type Answer1 = usize;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Puzzle, Error> {
+        input.parse()
+    }
+
+    fn part1(scan: &Self::Puzzle) -> Result<Self::Answer1, Error> {
+        let beacon_finder = BeaconFinder::new(&scan.sensor_beacons);
+        Ok(beacon_finder.find_impossible_beacon(&scan.row))
+    }
+
+    fn part2(scan: &Self::Puzzle) -> Result<Self::Answer2, Error> {
+        let beacon_finder = BeaconFinder::new(&scan.sensor_beacons);
+        let pos = beacon_finder.find_beacon_location_threaded::<4>(scan.max_row)?;
+        Ok(pos.get_score())
     }
 }
 
@@ -237,27 +229,32 @@ mod test {
 
     #[test]
     fn test_smarter_part1() {
-        let sensor_beacons: Result<Vec<SensorBeacon>, Error> = EXAMPLE.lines().map(str::trim_end).map(str::parse).collect();
-        let sensor_beacons = sensor_beacons.unwrap();
-
-        println!("{:?}", sensor_beacons);
+        let Scan { sensor_beacons, .. } = EXAMPLE.parse().unwrap();
 
         let beacon_finder = BeaconFinder::new(&sensor_beacons);
         let count = beacon_finder.find_impossible_beacon(&10);
-        println!("count: {}", count);
         assert_eq!(count, 26);
     }
 
     #[test]
     fn test_part_2() {
-        let sensor_beacons: Result<Vec<SensorBeacon>, Error> = EXAMPLE.lines().map(str::trim_end).map(str::parse).collect();
-        let sensor_beacons = sensor_beacons.unwrap();
-
-        println!("{:?}", sensor_beacons);
+        let Scan { sensor_beacons, .. } = EXAMPLE.parse().unwrap();
 
         let beacon_finder = BeaconFinder::new(&sensor_beacons);
-        let pos = beacon_finder.find_beacon_location_threaded::<4>(20);
-        println!("{:?}", pos);
+        let pos = beacon_finder.find_beacon_location_threaded::<4>(20).unwrap();
         assert_eq!(56000011, pos.get_score());
     }
+
+    #[test]
+    fn test_scan_parameters() {
+        let scan: Scan = EXAMPLE.parse().unwrap();
+        assert_eq!(14, scan.sensor_beacons.len());
+        assert_eq!(10, scan.row);
+        assert_eq!(20, scan.max_row);
+
+        assert_eq!(26, Day15::part1(&scan).unwrap());
+        assert_eq!(56000011, Day15::part2(&scan).unwrap());
+
+        assert_eq!(4_000_000, search_area(&[SensorBeacon(Vec2(3_999_960, 12), Vec2(0, 0))]).unwrap());
+    }
 }
@@ -1,11 +1,13 @@
 use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
+use crate::solver::Solver;
 use crate::utils::{Error, Surroundings};
-use crate::utils::bfs::{Graph, bfs};
-use crate::utils::minmax::MinMax;
+use crate::utils::bfs::filtered_bfs;
+use crate::utils::bounding_box::BoundingBox;
+use crate::utils::grid::{Grid, GridPos};
 use crate::utils::vec3::{Vec3, Vector3};
-use crate::utils::vec2::{Vec2, Vector2};
+use crate::utils::vec2::Vec2;
 
 pub type Pos = i8;
 
@@ -26,8 +28,8 @@ impl FromStr for Droplet {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut positions = HashSet::new();
 
-        for line in s.lines().map(str::trim_end) {
-            let pos: Vec3<Pos> = line.parse()?;
+        for (i, line) in s.lines().map(str::trim_end).enumerate() {
+            let pos: Vec3<Pos> = line.parse().map_err(|e: Error| e.with_line(i + 1))?;
             positions.insert(pos);
         }
 
@@ -41,7 +43,7 @@ impl SurfaceArea for Droplet {
         let mut counter = 0;
 
         for position in positions.iter() {
-            for surrounding in position.get_surroundings() {
+            for surrounding in Surroundings::<6>::get_surroundings(position) {
                 if !positions.contains(&surrounding) {
                     counter += 1;
                 }
@@ -62,28 +64,23 @@ impl OuterSurfaceArea for Droplet {
 
 impl Droplet {
 
-    pub fn min_max_x(&self) -> (&Pos, &Pos) {
+    pub fn bounding_box(&self) -> Option<BoundingBox<Vec3<Pos>>> {
         let Droplet(positions) = self;
-        positions.iter().map(|v| v.get_x()).min_max().unwrap()
-    }
-
-    pub fn min_max_y(&self) -> (&Pos, &Pos) {
-        let Droplet(positions) = self;
-        positions.iter().map(|v| v.get_y()).min_max().unwrap()
-    }
-
-    pub fn min_max_z(&self) -> (&Pos, &Pos) {
-        let Droplet(positions) = self;
-        positions.iter().map(|v| v.get_z()).min_max().unwrap()
+        BoundingBox::enclosing(positions.iter().cloned())
     }
 
     pub fn filled_droplet(&self) -> Droplet {
-        let (min_z, max_z) = self.min_max_z();
-
         let mut positions = HashSet::new();
 
-        for z in *min_z..=*max_z {
-            let slice = self.slice_z(z);
+        let Some(bounds) = self.bounding_box() else {
+            return Droplet(positions);
+        };
+
+        for z in bounds.z_range() {
+            // a layer without any cubes has nothing to fill
+            let Some(slice) = self.slice_z(z) else {
+                continue;
+            };
             for filled in slice.fill_empty_spots().into_iter().map(|v| v.extend(z)) {
                 positions.insert(filled);
             }
@@ -93,7 +90,7 @@ impl Droplet {
 
     }
 
-    pub fn slice_z(&self, height: Pos) -> DropletSlice {
+    pub fn slice_z(&self, height: Pos) -> Option<DropletSlice> {
         let Droplet(positions) = self;
 
         let result = positions.iter()
@@ -106,104 +103,78 @@ impl Droplet {
 
 }
 
+// a slice with a one cell margin around the cubes, so the outside is connected
 #[derive(Debug)]
 pub struct DropletSlice {
-    positions: HashSet<Vec2<Pos>>,
-    top_left: Vec2<Pos>,
-    bottom_right: Vec2<Pos>,
+    grid: Grid<bool>,
+    area: BoundingBox<Vec2<Pos>>,
 }
 
 impl DropletSlice {
 
-    pub fn new(pos: impl Iterator<Item=Vec2<Pos>>) -> DropletSlice {
-        let positions: HashSet<Vec2<Pos>> = pos.collect();
-        let (min_x, max_x) = positions.iter().map(|v| v.get_x()).min_max().unwrap();
-        let (min_y, max_y) = positions.iter().map(|v| v.get_y()).min_max().unwrap();
+    pub fn new(pos: impl Iterator<Item=Vec2<Pos>>) -> Option<DropletSlice> {
+        let positions: Vec<Vec2<Pos>> = pos.collect();
+        let area = BoundingBox::enclosing(positions.iter().cloned())?.padded(1);
 
-        let top_left = Vec2::new(*min_x, *min_y);
-        let bottom_right = Vec2::new(*max_x, *max_y);
+        let Vec2(width, height) = area.size();
+        let Vec2(ox, oy) = area.min();
 
-        DropletSlice {
-            positions,
-            top_left,
-            bottom_right,
+        let mut grid = Grid::filled(width as usize, height as usize, false);
+        for Vec2(x, y) in positions.iter() {
+            grid[&Vec2((x - ox) as usize, (y - oy) as usize)] = true;
         }
-    }
 
-    pub fn bounding_box(&self) -> (&Vec2<Pos>, &Vec2<Pos>) {
-        (&self.top_left, &self.bottom_right)
+        Some(DropletSlice {
+            grid,
+            area,
+        })
     }
 
-    pub fn set_top_left(&mut self, top_left: Vec2<Pos>) {
-        self.top_left = top_left;
+    fn to_droplet_pos(&self, pos: &GridPos) -> Vec2<Pos> {
+        let Vec2(x, y) = pos;
+        let Vec2(ox, oy) = self.area.min();
+        Vec2(ox + *x as Pos, oy + *y as Pos)
     }
 
-    pub fn set_bottom_right(&mut self, bottom_right: Vec2<Pos>) {
-        self.bottom_right = bottom_right;
+    // the bounds of the cubes, without the margin
+    pub fn bounding_box(&self) -> BoundingBox<Vec2<Pos>> {
+        self.area.padded(-1)
     }
 
     pub fn fill_empty_spots(&self) -> HashSet<Vec2<Pos>> {
-        let (mv@Vec2(min_x, min_y), Vec2(max_x, max_y)) = self.bounding_box();
-
-        let reachable = bfs(self, &mv.map(|v| v.saturating_sub(1)));
-
-        let mut filled_positions: HashSet<Vec2<Pos>> = HashSet::new();
-
-        for y in *min_y..=*max_y {
-            for x in *min_x..=*max_x {
-                let pos = Vec2::new(x, y);
-                if !reachable.contains_key(&pos) {
-                    filled_positions.insert(pos);
-                }
-            }
-        }
-
-        filled_positions
+        let reachable = filtered_bfs(&self.grid, |_, next| !*next, &Vec2(0, 0));
 
+        self.grid.positions()
+            .filter(|pos| !reachable.contains_key(pos))
+            .map(|pos| self.to_droplet_pos(&pos))
+            .collect()
     }
 
 }
 
-impl Graph for DropletSlice {
-    type Position = Vec2<Pos>;
-    type Property = ();
-
-    fn get_property(&self, _: &Self::Position) -> &Self::Property {
-        &()
+impl Display for DropletSlice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.grid.render(|filled| if *filled { '#' } else { '.' }))
     }
+}
 
-    fn get_surroundings(&self, pos: &Self::Position) -> Vec<Self::Position> {
-        let Vec2(min_x, min_y) = self.top_left.map(|v| v.saturating_sub(2));
-        let Vec2(max_x, max_y) = self.bottom_right.map(|v| v.saturating_add(2));
+pub struct Day18;
 
-        pos.get_surroundings().iter().filter(|p| {
-            p.get_x() >= &min_x &&
-                p.get_y() >= &min_y &&
-                p.get_x() <= &max_x &&
-                p.get_y() <= &max_y &&
-                !self.positions.contains(p)
-        }).map(|v| v.clone()).collect()
-    }
-}
+impl Solver for Day18 {
+    type Puzzle = Droplet;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-impl Display for DropletSlice {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let (Vec2(min_x, min_y), Vec2(max_x, max_y)) = self.bounding_box();
-
-        for y in *min_y..=*max_y {
-            for x in *min_x..=*max_x {
-                let char = if self.positions.contains(&Vec2::new(x, y)) {
-                    "#"
-                } else {
-                    "."
-                };
-                write!(f, "{}", char)?;
-            }
-            write!(f, "\n")?;
-        }
+    fn parse(input: &str) -> Result<Self::Puzzle, Error> {
+        input.parse()
+    }
 
-        Ok(())
+    fn part1(droplet: &Self::Puzzle) -> Result<Self::Answer1, Error> {
+        Ok(droplet.calc_surface_area())
+    }
 
+    fn part2(droplet: &Self::Puzzle) -> Result<Self::Answer2, Error> {
+        Ok(droplet.calc_outer_surface_area())
     }
 }
 
@@ -226,9 +197,7 @@ mod test {
 
         println!("hollow");
         for i in 1..=6 {
-            let mut slice = droplet.slice_z(i);
-            slice.set_top_left(Vec2(1, 1));
-            slice.set_bottom_right(Vec2(3, 3));
+            let slice = droplet.slice_z(i).unwrap();
             println!("{}", slice);
         }
 
@@ -236,14 +205,19 @@ mod test {
 
         println!("filled");
         for i in 1..=6 {
-            let mut slice = filled_droplet.slice_z(i);
-            slice.set_top_left(Vec2(1, 1));
-            slice.set_bottom_right(Vec2(3, 3));
+            let slice = filled_droplet.slice_z(i).unwrap();
             println!("{}", slice);
         }
 
         let outer_surface_area = droplet.calc_outer_surface_area();
         assert_eq!(58, outer_surface_area);
+
+        assert!(droplet.slice_z(100).is_none());
+        assert_eq!(BoundingBox::new(Vec2(1, 1), Vec2(3, 3)), droplet.slice_z(2).unwrap().bounding_box());
+        assert_eq!(Some(BoundingBox::new(Vec3(1, 1, 1), Vec3(3, 3, 6))), droplet.bounding_box());
+
+        let gapped: Droplet = "1,1,1\n1,1,3".parse().unwrap();
+        assert_eq!(12, gapped.calc_outer_surface_area());
     }
 
 }
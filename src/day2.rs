@@ -2,6 +2,7 @@ use std::cmp::Ordering;
 use std::str::FromStr;
 
 use crate::{Error, Scored};
+use crate::solver::Solver;
 
 #[derive(Debug, Eq, PartialEq)]
 pub enum OpponentChoice {
@@ -56,7 +57,7 @@ impl FromStr for OpponentChoice {
             "A" => Ok(OpponentChoice::A),
             "B" => Ok(OpponentChoice::B),
             "C" => Ok(OpponentChoice::C),
-            _ => Err(Error(format!("invalid choice: {}", s)))
+            _ => Err(Error::parse(format!("invalid choice: {}", s)))
         }
     }
 }
@@ -69,7 +70,7 @@ impl FromStr for MyChoice {
             "X" => Ok(MyChoice::X),
             "Y" => Ok(MyChoice::Y),
             "Z" => Ok(MyChoice::Z),
-            _ => Err(Error(format!("invalid choice: {}", s)))
+            _ => Err(Error::parse(format!("invalid choice: {}", s)))
         }
     }
 }
@@ -119,11 +120,12 @@ impl FromStr for StrategyGuide {
 
         let mut result = Vec::new();
 
-        for line in s.lines() {
-            let (opponent_choice, my_choice) = line.trim_end().split_once(" ").ok_or(Error("invalid line".to_string()))?;
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim_end();
+            let (opponent_choice, my_choice) = line.split_once(" ").ok_or_else(|| Error::parse("invalid line").with_line(i + 1))?;
 
-            let opponent_choice: OpponentChoice = opponent_choice.parse()?;
-            let my_choice: MyChoice = my_choice.parse()?;
+            let opponent_choice: OpponentChoice = opponent_choice.parse().map_err(|e: Error| e.at(line, opponent_choice).with_line(i + 1))?;
+            let my_choice: MyChoice = my_choice.parse().map_err(|e: Error| e.at(line, my_choice).with_line(i + 1))?;
 
             result.push( (opponent_choice, my_choice) );
         }
@@ -199,6 +201,26 @@ impl Scored for Game {
     }
 }
 
+pub struct Day2;
+
+impl Solver for Day2 {
+    type Puzzle = StrategyGuide;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Puzzle, Error> {
+        input.parse()
+    }
+
+    fn part1(guide: &Self::Puzzle) -> Result<Self::Answer1, Error> {
+        Ok(guide.evaluate_game::<BasicEvaluator>().get_score())
+    }
+
+    fn part2(guide: &Self::Puzzle) -> Result<Self::Answer2, Error> {
+        Ok(guide.evaluate_game::<AdvancedEvaluator>().get_score())
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -216,7 +238,10 @@ mod test {
     fn test_invalid_conversion() {
         let result : Result<MyChoice, Error> = "i".parse();
         assert!(result.is_err());
-        assert_eq!("invalid choice: i", result.unwrap_err().0)
+        assert_eq!("invalid choice: i", result.unwrap_err().to_string());
+
+        let result: Result<StrategyGuide, Error> = "A X\nB Q\n".parse();
+        assert_eq!("line 2, column 3: invalid choice: Q", result.unwrap_err().to_string());
     }
 
 
@@ -1,7 +1,9 @@
 use std::ops::Range;
 use std::str::FromStr;
 
-use crate::utils::Error;
+use crate::solver::Solver;
+use crate::utils::{Error, parse_lines};
+use crate::utils::num::rem_euclid;
 
 pub type Number = isize;
 
@@ -55,19 +57,10 @@ impl EncryptedFile {
 
         // println!("virt_cur: {}", virt_cur);
 
-        let virt_dest = virt_cur + (rel * 2);
-        let virt_dest = virt_dest % double_len;
-
-        let mut virt_dest = virt_dest;
-
-        while virt_dest < 0 {
-            virt_dest += double_len;
-        }
+        let virt_dest = rem_euclid(virt_cur + (rel * 2), double_len);
 
         // println!("virt dest: {}", virt_dest);
 
-        let virt_dest = virt_dest;
-
         let dest = if virt_dest < virt_cur {
             (virt_dest + 1) / 2
         } else {
@@ -76,8 +69,6 @@ impl EncryptedFile {
 
         // println!("dest: {}", dest);
 
-        assert!(dest >= 0);
-
         dest as usize
     }
 
@@ -105,11 +96,11 @@ impl EncryptedFile {
         self.indices.iter().map(|i| &self.numbers[*i]).collect()
     }
 
-    pub fn decrypt(&mut self) -> Option<usize> {
+    pub fn decrypt(&mut self) -> Result<usize, Error> {
         self.decrypt_range(0..self.len())
     }
 
-    pub fn decrypt_range(&mut self, range: Range<usize>) -> Option<usize> {
+    pub fn decrypt_range(&mut self, range: Range<usize>) -> Result<usize, Error> {
 
         let mut zero_pos = None;
 
@@ -117,7 +108,9 @@ impl EncryptedFile {
             let displacement = *self.original_content()[i];
 
             if displacement == 0 {
-                assert!(zero_pos.is_none());
+                if zero_pos.is_some() {
+                    return Err(Error::invalid_state("more than one zero in file"));
+                }
                 zero_pos = Some(i)
             }
 
@@ -131,7 +124,7 @@ impl EncryptedFile {
             // println!("round {}, displacement {}, index_of {} destination {}, afterr: {:?}", i, displacement, index_of, destination, self.content());
         }
 
-        zero_pos.map(|v| self.index_of(v))
+        zero_pos.map(|v| self.index_of(v)).ok_or_else(|| Error::invalid_state("no zero in file"))
     }
 
     pub fn access_at_wrapping(&self, index: usize) -> &Number {
@@ -151,12 +144,39 @@ impl FromStr for EncryptedFile {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let ef: EncryptedFile = s.lines().map(str::trim_end).map(|l| l.parse::<Number>().unwrap()).collect();
-        Ok(ef)
+        let numbers: Vec<Number> = parse_lines(s)?;
+        Ok(EncryptedFile::new(numbers))
     }
 }
 
 
+pub struct Day20;
+
+impl Solver for Day20 {
+    type Puzzle = Vec<Number>;
+    type Answer1 = Number;
+    type Answer2 = Number;
+
+    fn parse(input: &str) -> Result<Self::Puzzle, Error> {
+        parse_lines(input)
+    }
+
+    fn part1(numbers: &Self::Puzzle) -> Result<Self::Answer1, Error> {
+        let mut ef = EncryptedFile::new(numbers.clone());
+
+        let zero_pos = ef.decrypt()?;
+
+        Ok([1000, 2000, 3000].into_iter()
+            .map(|v| v + zero_pos)
+            .map(|v| ef.access_at_wrapping(v))
+            .sum())
+    }
+
+    fn part2(_: &Self::Puzzle) -> Result<Self::Answer2, Error> {
+        Err(Error::invalid_state("part 2 is not solved yet"))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -182,9 +202,8 @@ mod test {
         let zero_pos = ef.decrypt();
         assert_eq!(&vec![1, 2, -3, 4, 0, 3, -2], ef.content());
 
-        assert_eq!(Some(4), zero_pos);
-
         let zero_pos = zero_pos.unwrap();
+        assert_eq!(4, zero_pos);
 
         let coordinates: Vec<&Number> = vec![1000, 2000, 3000].into_iter()
                 .map(|v| v + zero_pos)
@@ -200,7 +219,7 @@ mod test {
     fn test_move_around_2() {
         let mut ef: EncryptedFile = EncryptedFile::new(vec![-3, -3, 5, 6, 7, 8, 9]);
 
-        ef.decrypt();
+        assert!(ef.decrypt().is_err());
 
         println!("{:?}", ef.content())
     }
@@ -2,6 +2,9 @@ use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 use crate::Error;
+use crate::utils::parse_lines;
+use crate::utils::parser::{parse_with, Parser};
+use crate::solver::Solver;
 
 #[derive(Debug, Copy, Clone)]
 pub enum Operation {
@@ -13,13 +16,15 @@ pub enum Operation {
 
 impl Operation {
 
-    pub fn eval(&self, lhs: i64, rhs: i64) -> i64 {
-        match self {
-            Operation::Add => lhs + rhs,
-            Operation::Sub => lhs - rhs,
-            Operation::Mul => lhs * rhs,
-            Operation::Div => lhs / rhs,
-        }
+    pub fn eval(&self, lhs: i64, rhs: i64) -> Result<i64, Error> {
+        let result = match self {
+            Operation::Add => lhs.checked_add(rhs),
+            Operation::Sub => lhs.checked_sub(rhs),
+            Operation::Mul => lhs.checked_mul(rhs),
+            Operation::Div => lhs.checked_div(rhs),
+        };
+
+        result.ok_or_else(|| Error::invalid_state(format!("cannot evaluate {} {} {}", lhs, self, rhs)))
     }
     
     pub fn invert(&self) -> Operation {
@@ -97,21 +102,21 @@ impl FromStr for MonkeyAction {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.parse::<i64>() {
-            Ok(number) => Ok(MonkeyAction::Value(number)),
-            Err(_) => {
-                let splits: Vec<&str> = s.splitn(3, " ").collect();
-                let id1 = splits.get(0).ok_or(Error::cannot_parse(s))?.to_string();
-                let op: Operation = splits[1].parse()?;
-                let id2 = splits.get(2).ok_or(Error::cannot_parse(s))?.to_string();
-
-                Ok(MonkeyAction::Instruction {
-                    id1,
-                    op,
-                    id2,
-                })
+        parse_with(s, |p| {
+            if let Some(number) = p.optional(Parser::integer) {
+                return Ok(MonkeyAction::Value(number));
             }
-        }
+
+            let id1 = p.token()?.to_string();
+            let op = p.parse_token()?;
+            let id2 = p.token()?.to_string();
+
+            Ok(MonkeyAction::Instruction {
+                id1,
+                op,
+                id2,
+            })
+        })
     }
 }
 
@@ -119,13 +124,17 @@ impl FromStr for MonkeyDefinition {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (id, action) = s.split_once(":").ok_or(Error::cannot_parse(s))?;
-
-        let monkey_action: MonkeyAction = action.trim().parse()?;
+        parse_with(s, |p| {
+            let id = p.take_while(char::is_alphanumeric);
+            if id.is_empty() {
+                return Err(p.error("expected a monkey id"));
+            }
+            p.literal(":")?;
 
-        Ok(MonkeyDefinition {
-            id: id.to_string(),
-            action: monkey_action
+            Ok(MonkeyDefinition {
+                id: id.to_string(),
+                action: p.parse()?,
+            })
         })
     }
 }
@@ -137,8 +146,8 @@ impl FromStr for MonkeyDefinitions {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let definitions: Result<Vec<MonkeyDefinition>, Error> = s.lines().map(str::trim_end).map(|s| s.parse()).collect();
-        let definitions = definitions?.into_iter().map(|d| (d.id.clone(), d)).collect();
+        let definitions: Vec<MonkeyDefinition> = parse_lines(s)?;
+        let definitions = definitions.into_iter().map(|d| (d.id.clone(), d)).collect();
         Ok(MonkeyDefinitions(definitions))
     }
 }
@@ -171,8 +180,12 @@ impl MonkeyDefinitions {
             .collect()
     }
 
-    pub fn eval(&self) -> i64 {
+    fn get(&self, id: &str) -> Result<&MonkeyDefinition, Error> {
         let MonkeyDefinitions(definitions) = self;
+        definitions.get(id).ok_or_else(|| Error::invalid_state(format!("unknown monkey: {}", id)))
+    }
+
+    pub fn eval(&self) -> Result<i64, Error> {
         let mut value_cache: HashMap<&str, i64> = self.get_initial_value_cache();
         let mut eval_stack: Vec<&str> = Vec::new();
 
@@ -184,14 +197,14 @@ impl MonkeyDefinitions {
             }
 
             eval_stack.push(last);
-            let def = &definitions[last];
+            let def = self.get(last)?;
             if let MonkeyAction::Instruction {id1, op, id2} = &def.action {
                 let id1 = id1.as_str();
                 let id2 = id2.as_str();
                 if value_cache.contains_key(id1) && value_cache.contains_key(id2) {
                     let lhs = value_cache[id1];
                     let rhs = value_cache[id2];
-                    value_cache.insert(last, op.eval(lhs, rhs));
+                    value_cache.insert(last, op.eval(lhs, rhs)?);
                 } else {
                     if !value_cache.contains_key(id1) {
                         eval_stack.push(id1)
@@ -201,22 +214,22 @@ impl MonkeyDefinitions {
                     }
                 }
             } else {
-                panic!();
+                return Err(Error::invalid_state(format!("monkey {} has no value", last)));
             }
         }
 
-        value_cache["root"]
+        value_cache.get("root").copied().ok_or_else(|| Error::invalid_state("root has no value"))
     }
 
-    pub fn human_eval(&self) -> (Value, Value) {
-        let MonkeyDefinitions(definitions) = self;
+    pub fn human_eval(&self) -> Result<(Value, Value), Error> {
         let mut value_cache: HashMap<&str, Value> = self.get_initial_value_cache().into_iter()
             .map(|(k, v)| (k, Value::Value(v))).collect();
         let mut eval_stack: Vec<&str> = Vec::new();
 
         value_cache.insert("humn", Value::X);
 
-        let (root_id1, root_id2) = definitions["root"].action.instruction_ids().unwrap();
+        let (root_id1, root_id2) = self.get("root")?.action.instruction_ids()
+            .ok_or_else(|| Error::invalid_state("root is not an instruction"))?;
 
         eval_stack.push(root_id1);
         eval_stack.push(root_id2);
@@ -228,7 +241,7 @@ impl MonkeyDefinitions {
 
             eval_stack.push(last);
 
-            let def = &definitions[last];
+            let def = self.get(last)?;
             if let MonkeyAction::Instruction {id1, op, id2} = &def.action {
                 let id1 = id1.as_str();
                 let id2 = id2.as_str();
@@ -238,7 +251,7 @@ impl MonkeyDefinitions {
                     let rhs = &value_cache[id2];
 
                     let result = match (lhs, rhs) {
-                        (Value::Value(v1), Value::Value(v2)) => Value::Value(op.eval(*v1, *v2)),
+                        (Value::Value(v1), Value::Value(v2)) => Value::Value(op.eval(*v1, *v2)?),
                         (xop1, xop2) => Value::XOp(Box::new(xop1.clone()), op.clone(), Box::new(xop2.clone())),
                     };
                     value_cache.insert(last, result);
@@ -251,18 +264,21 @@ impl MonkeyDefinitions {
                     }
                 }
 
+            } else {
+                return Err(Error::invalid_state(format!("monkey {} has no value", last)));
             }
         }
 
-        let v1 = value_cache.remove(root_id1).unwrap();
-        let v2 = value_cache.remove(root_id2).unwrap();
+        let missing = |id: &str| Error::invalid_state(format!("monkey {} has no value", id));
+        let v1 = value_cache.remove(root_id1).ok_or_else(|| missing(root_id1))?;
+        let v2 = value_cache.remove(root_id2).ok_or_else(|| missing(root_id2))?;
 
-        (v1, v2)
+        Ok((v1, v2))
     }
 
 }
 
-pub fn solve(v1: &Value, v2: i64) -> i64 {
+pub fn solve(v1: &Value, v2: i64) -> Result<i64, Error> {
     
     let mut cur_v1 = v1.clone();
     let mut cur_v2 = v2.clone();
@@ -274,19 +290,43 @@ pub fn solve(v1: &Value, v2: i64) -> i64 {
             
             cur_v2 = match op {
                 Operation::Sub | Operation::Div => {
-                    op.eval(v, cur_v2)
+                    op.eval(v, cur_v2)?
                 },
-                _ => inv_op.eval(cur_v2, v),
+                _ => inv_op.eval(cur_v2, v)?,
             };
         } else if let Value::Value(v) = *op2 {
             cur_v1 = *op1;
-            cur_v2 = inv_op.eval(cur_v2, v);
+            cur_v2 = inv_op.eval(cur_v2, v)?;
         } else {
-            panic!();
+            return Err(Error::invalid_state("both operands depend on humn"));
         }
     }
     
-    cur_v2
+    Ok(cur_v2)
+}
+
+pub struct Day21;
+
+impl Solver for Day21 {
+    type Puzzle = MonkeyDefinitions;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Puzzle, Error> {
+        input.parse()
+    }
+
+    fn part1(defs: &Self::Puzzle) -> Result<Self::Answer1, Error> {
+        defs.eval()
+    }
+
+    fn part2(defs: &Self::Puzzle) -> Result<Self::Answer2, Error> {
+        match defs.human_eval()? {
+            (lhs, Value::Value(v)) => solve(&lhs, v),
+            (Value::Value(v), rhs) => solve(&rhs, v),
+            _ => Err(Error::invalid_state("both sides of root depend on humn")),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -304,19 +344,28 @@ mod test {
     #[test]
     fn test_eval() {
         let defs: MonkeyDefinitions = EXAMPLE.parse().unwrap();
-        let code = defs.eval();
+        let code = defs.eval().unwrap();
         // println!("{}", code);
         assert_eq!(152, code);
+
+        let defs: MonkeyDefinitions = "root: abcd + efgh\nabcd: 1".parse().unwrap();
+        assert_eq!("unknown monkey: efgh", defs.eval().unwrap_err().to_string());
+
+        let error = "root: abcd ? efgh".parse::<MonkeyDefinitions>().unwrap_err();
+        assert_eq!("line 1, column 12: cannot parse ?", error.to_string());
+
+        let defs: MonkeyDefinitions = "root: abcd / efgh\nabcd: 1\nefgh: 0".parse().unwrap();
+        assert!(defs.eval().is_err());
     }
 
     #[test]
     fn test_eval_2() {
         let defs: MonkeyDefinitions = EXAMPLE.parse().unwrap();
-        let (lhs, rhs) = defs.human_eval();
+        let (lhs, rhs) = defs.human_eval().unwrap();
         println!("{}", lhs);
         println!("{}", rhs);
         if let Value::Value(v) = rhs {
-            println!("solved: {}", solve(&lhs, v));
+            assert_eq!(301, solve(&lhs, v).unwrap());
         }
     }
 }
@@ -1,10 +1,12 @@
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
-use std::mem::replace;
-use std::num::ParseIntError;
 use std::ops::RangeInclusive;
 use std::str::FromStr;
 use crate::{Error, Scored};
+use crate::solver::Solver;
+use crate::utils::parse_at;
+use crate::utils::bounding_box::BoundingBox;
+use crate::utils::parser::{parse_with, Parser};
 use crate::utils::minmax::MinMax;
 pub use crate::utils::turtle::*;
 use crate::utils::vec2::*;
@@ -31,20 +33,21 @@ impl Display for Tile {
 #[derive(Debug)]
 pub struct Map {
     map: HashMap<PosVec2, Tile>,
-    bounding_box: BoundingBox<Pos>,
+    bounding_box: BoundingBox<PosVec2>,
     row_ranges: HashMap<Pos, RangeInclusive<Pos>>,
     col_ranges: HashMap<Pos, RangeInclusive<Pos>>,
     regions: Option<Vec<Region>>,
 }
 
 impl Map {
-    pub fn get_starting_position(&self) -> PosVec2 {
+    pub fn get_starting_position(&self) -> Result<PosVec2, Error> {
         let top_row_idx = *self.bounding_box.y_range().start();
-        let top_row_bounds = self.row_ranges[&top_row_idx].clone();
-        top_row_bounds
+        let top_row_bounds = self.row_ranges.get(&top_row_idx)
+            .ok_or_else(|| Error::invalid_state("no top row found"))?;
+        top_row_bounds.clone()
             .map(|b| PosVec2::new(b, top_row_idx))
             .find(|p| self.map.get(p) == Some(&Tile::Floor))
-            .expect("no starting position found")
+            .ok_or_else(|| Error::invalid_state("no starting position found"))
     }
 
     pub fn set_regions(&mut self, regions: Option<Vec<Region>>) {
@@ -72,13 +75,11 @@ impl Map {
         }
     }
 
-    pub fn wrap_region(&self, old_pos: &PosVec2, dir: &Direction, new_pos: &mut PosVec2) {
-        assert!(self.regions.is_some());
-
-        let regions = self.regions.as_ref().unwrap();
+    pub fn wrap_region(&self, old_pos: &PosVec2, dir: &Direction, _new_pos: &mut PosVec2) -> Result<(), Error> {
+        let regions = self.regions.as_ref().ok_or_else(|| Error::invalid_state("no regions defined"))?;
 
         let source_region = regions.iter().find(|r| r.bounding_box.contains(old_pos))
-            .expect(&format!("no region contains {:?}", old_pos));
+            .ok_or_else(|| Error::invalid_state(format!("no region contains {:?}", old_pos)))?;
 
         let lookup = match dir {
             Direction::Right => 0,
@@ -90,8 +91,8 @@ impl Map {
         let new_region = source_region.region_mapping[lookup];
         let dir_in_new_region = source_region.direction_mapping[lookup];
 
-        let new_region = regions.iter().find(|r| r.id == new_region)
-            .expect(&format!("invalid region: {:?}", new_region));
+        let _new_region = regions.iter().find(|r| r.id == new_region)
+            .ok_or_else(|| Error::invalid_state(format!("invalid region: {:?}", new_region)))?;
 
         match dir_in_new_region {
             Direction::Right => {}
@@ -99,13 +100,14 @@ impl Map {
             Direction::Left => {}
             Direction::Down => {}
         }
+
+        Ok(())
     }
 }
 
 impl From<HashMap<PosVec2, Tile>> for Map {
     fn from(map: HashMap<PosVec2, Tile>) -> Self {
-        let bounding_box: BoundingBox<&Pos> = map.keys().collect();
-        let bounding_box = bounding_box.map(|v| *v);
+        let bounding_box: BoundingBox<PosVec2> = map.keys().collect();
 
         let mut row_ranges = HashMap::new();
         let mut col_ranges = HashMap::new();
@@ -159,6 +161,10 @@ impl FromStr for Map {
             }
         }
 
+        if map.is_empty() {
+            return Err(Error::parse("empty map"));
+        }
+
         Ok(map.into())
     }
 }
@@ -198,27 +204,28 @@ impl FromStr for Instructions {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut instructions = Vec::new();
-        let mut number_cache = String::new();
+        let mut number_start: Option<usize> = None;
 
-        for c in s.chars() {
+        for (i, c) in s.char_indices() {
             match c {
-                '0' | '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' => number_cache.push(c),
+                '0' | '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' => {
+                    number_start.get_or_insert(i);
+                }
                 'L' | 'R' => {
-                    if !number_cache.is_empty() {
-                        let number = replace(&mut number_cache, String::new());
-                        let number: usize = number.parse()?;
+                    if let Some(start) = number_start.take() {
+                        let number: usize = parse_at(s, &s[start..i])?;
                         instructions.push(Instruction::Step(number));
-                        let turn: Turn = c.to_string().parse()?;
+                        let turn: Turn = s[i..i + 1].parse().map_err(|e: Error| e.with_column(i + 1))?;
                         instructions.push(Instruction::Turn(turn));
                     }
                 }
                 _ => {
-                    return Err(Error::cannot_parse(&c));
+                    return Err(Error::cannot_parse(&c).with_column(i + 1));
                 }
             }
         }
-        if !number_cache.is_empty() {
-            let number: usize = number_cache.parse()?;
+        if let Some(start) = number_start {
+            let number: usize = parse_at(s, &s[start..])?;
             instructions.push(Instruction::Step(number));
         }
 
@@ -292,7 +299,7 @@ impl Scored for Turtle<'_, Map> {
 #[derive(Debug)]
 pub struct Region {
     id: usize,
-    bounding_box: BoundingBox<Pos>,
+    bounding_box: BoundingBox<PosVec2>,
     region_mapping: [usize; 4],
     direction_mapping: [Direction; 4],
 }
@@ -301,46 +308,57 @@ impl FromStr for Region {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (id, rest) = s.split_once(":").ok_or(Error::cannot_parse(s))?;
-        let (bb, mapping) = rest.split_once(";").ok_or(Error::cannot_parse(rest))?;
-        let (start, end) = bb.split_once("-").ok_or(Error::cannot_parse(bb))?;
-        let (region_mapping, direction_mapping) = mapping.split_once("-").ok_or(Error::cannot_parse(mapping))?;
-
-        let id: usize = id.parse()?;
-        let start: PosVec2 = start.parse()?;
-        let end: PosVec2 = end.parse()?;
-
-
-
-        let region_mapping: Result<Vec<usize>, ParseIntError> =  region_mapping.chars().map(|c| c.to_string().parse::<usize>()).collect();
-        let region_mapping = region_mapping?;
-
-        let region_mapping = [
-            region_mapping[0],
-            region_mapping[1],
-            region_mapping[2],
-            region_mapping[3]
-        ];
-
-        let direction_mapping: Result<Vec<Direction>, Error> = direction_mapping.chars().map(|c| c.to_string().parse::<Direction>()).collect();
-        let direction_mapping = direction_mapping?;
-
-        let direction_mapping = [
-            direction_mapping[0],
-            direction_mapping[1],
-            direction_mapping[2],
-            direction_mapping[3],
-        ];
-
-        Ok(Region {
-            id,
-            bounding_box: BoundingBox::new(start, end),
-            region_mapping,
-            direction_mapping,
+        parse_with(s, |p| {
+            let id = p.integer()?;
+            p.literal(":")?;
+            let [start_x, start_y] = p.array(",", Parser::integer)?;
+            p.literal("-")?;
+            let [end_x, end_y] = p.array(",", Parser::integer)?;
+            p.literal(";")?;
+            let region_mapping = p.array("", Parser::parse_char)?;
+            p.literal("-")?;
+            let direction_mapping = p.array("", Parser::parse_char)?;
+
+            Ok(Region {
+                id,
+                bounding_box: BoundingBox::new(PosVec2::new(start_x, start_y), PosVec2::new(end_x, end_y)),
+                region_mapping,
+                direction_mapping,
+            })
         })
     }
 }
 
+pub struct Day22;
+
+impl Solver for Day22 {
+    type Puzzle = (Map, Instructions);
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Puzzle, Error> {
+        let map: Map = input.parse()?;
+
+        let instructions = input.lines()
+            .skip_while(|l| !l.trim().is_empty())
+            .find(|l| !l.trim().is_empty())
+            .ok_or_else(|| Error::parse("missing instructions"))?;
+
+        let instructions = instructions.trim_end();
+        Ok((map, instructions.parse().map_err(|e: Error| e.at(input, instructions))?))
+    }
+
+    fn part1((map, instructions): &Self::Puzzle) -> Result<Self::Answer1, Error> {
+        let mut turtle = Turtle::new(map, map.get_starting_position()?, Direction::Right);
+        instructions.simulate(&mut turtle);
+        Ok(turtle.get_score())
+    }
+
+    fn part2(_: &Self::Puzzle) -> Result<Self::Answer2, Error> {
+        Err(Error::invalid_state("part 2 is not solved yet"))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -352,6 +370,14 @@ mod test {
         let map: Map = EXAMPLE.parse().unwrap();
 
         println!("{}", map);
+
+        let map: Map = "  #..\n  ...".parse().unwrap();
+        assert_eq!(PosVec2::new(4, 1), map.get_starting_position().unwrap());
+
+        let map: Map = "###\n...".parse().unwrap();
+        assert!(map.get_starting_position().is_err());
+
+        assert!("\n10R5".parse::<Map>().is_err());
     }
 
     #[test]
@@ -368,6 +394,18 @@ mod test {
         println!("{:#?}", instructions);
     }
 
+    #[test]
+    fn test_parse_regions() {
+        let regions: Vec<Region> = crate::utils::parse_lines(include_str!("../res/day22-regions_example.txt")).unwrap();
+
+        assert_eq!(6, regions.len());
+        assert_eq!([6, 2, 3, 4], regions[0].region_mapping);
+        assert!(regions[0].bounding_box.contains(&PosVec2::new(4, 12)));
+
+        let error = "1:1,9-4,12;6234;RUUU".parse::<Region>().unwrap_err();
+        assert_eq!("column 16: expected - but got ;RUUU", error.to_string());
+    }
+
     #[test]
     fn test_simulate() {
         let map: Map = EXAMPLE.parse().unwrap();
@@ -381,7 +419,7 @@ mod test {
         let instructions = lines.next().unwrap();
         let instructions: Instructions = instructions.parse().unwrap();
 
-        let starting_position = map.get_starting_position();
+        let starting_position = map.get_starting_position().unwrap();
 
         let mut turtle = Turtle::new(&map, starting_position, Direction::Right);
 
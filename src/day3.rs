@@ -1,22 +1,32 @@
-use std::collections::HashSet;
+use crate::{Error, Scored};
+use crate::solver::Solver;
+use crate::utils::bv::BitVec;
 
-use crate::Scored;
+fn item_set(items: &str) -> BitVec {
+    items.bytes().map(usize::from).collect()
+}
 
-pub fn find_duplicate(line: &str) -> char {
-    assert_eq!(line.len() % 2, 0);
+fn single_item(items: &BitVec) -> Option<char> {
+    match items.iter_ones().collect::<Vec<usize>>()[..] {
+        [c] => u8::try_from(c).ok().map(char::from),
+        _ => None,
+    }
+}
+
+pub fn find_duplicate(line: &str) -> Result<char, Error> {
+    if !line.len().is_multiple_of(2) || !line.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(Error::parse(format!("invalid rucksack: {}", line)));
+    }
 
     let midpoint = line.len() / 2;
 
     let slice1 = &line[..midpoint];
     let slice2 = &line[midpoint..];
 
-    let slice1_chars: HashSet<char> = slice1.chars().collect();
-    let slice2_chars: HashSet<char> = slice2.chars().collect();
+    let repeat_items = &item_set(slice1) & &item_set(slice2);
 
-    let repeat_char: Vec<&char> = slice1_chars.intersection(&slice2_chars).collect();
-
-    assert_eq!(repeat_char.len(), 1);
-    *repeat_char[0]
+    single_item(&repeat_items)
+        .ok_or_else(|| Error::invalid_state(format!("expected exactly one duplicate in: {}", line)))
 }
 
 impl Scored for char {
@@ -33,7 +43,7 @@ impl Scored for char {
     }
 }
 
-fn chunked_iteration<'a>(lines: &mut impl Iterator<Item = &'a str>) -> Vec<char> {
+fn chunked_iteration<'a>(lines: &mut impl Iterator<Item = &'a str>) -> Result<Vec<char>, Error> {
 
     let mut result = Vec::new();
 
@@ -48,51 +58,72 @@ fn chunked_iteration<'a>(lines: &mut impl Iterator<Item = &'a str>) -> Vec<char>
             break;
         }
 
-        let hashsets: Vec<HashSet<char>> = group.iter()
-            .map(|o| o.unwrap())
-            .map(|l| l.chars().collect::<HashSet<char>>())
+        let item_sets: Vec<BitVec> = group.iter()
+            .flatten()
+            .map(|l| item_set(l))
             .collect();
 
-        let intersection1: HashSet<char> = hashsets[0].intersection(&hashsets[1]).map(|v| *v).collect();
-        let intersection2: Vec<&char> = intersection1.intersection(&hashsets[2]).collect();
+        let intersection = &(&item_sets[0] & &item_sets[1]) & &item_sets[2];
 
-        assert_eq!(intersection2.len(), 1);
-        result.push(*intersection2[0]);
+        match single_item(&intersection) {
+            Some(c) if c.is_ascii_alphabetic() => result.push(c),
+            _ => return Err(Error::invalid_state(format!("expected exactly one badge in group: {:?}", group))),
+        }
     }
 
-    result
+    Ok(result)
 }
 
 #[derive(Debug)]
-pub struct Day3Input(pub &'static str);
+pub struct Day3Input<'a>(pub &'a str);
 
-impl Scored for Day3Input {
-    fn get_score(&self) -> u64 {
+impl Day3Input<'_> {
+    pub fn get_score(&self) -> Result<u64, Error> {
         let Day3Input(input) = self;
         input.lines()
             .map(str::trim_end)
-            .map(find_duplicate)
-            .map(|c| c.get_score())
+            .map(|l| find_duplicate(l).map(|c| c.get_score()))
             .sum()
     }
 }
 
 #[derive(Debug)]
-pub struct Day3Chunked(pub &'static str);
+pub struct Day3Chunked<'a>(pub &'a str);
 
-impl From<Day3Input> for Day3Chunked {
-    fn from(input: Day3Input) -> Self {
+impl<'a> From<Day3Input<'a>> for Day3Chunked<'a> {
+    fn from(input: Day3Input<'a>) -> Self {
         let Day3Input(value) = input;
         Day3Chunked(value)
     }
 }
 
-impl Scored for Day3Chunked {
-    fn get_score(&self) -> u64 {
+impl Day3Chunked<'_> {
+    pub fn get_score(&self) -> Result<u64, Error> {
         let Day3Chunked(input) = self;
-        chunked_iteration(&mut input.lines()).iter()
+        Ok(chunked_iteration(&mut input.lines())?.iter()
             .map(|c| c.get_score())
-            .sum()
+            .sum())
+    }
+}
+
+pub struct Day3;
+
+impl Solver for Day3 {
+    type Puzzle = String;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Puzzle, Error> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Puzzle) -> Result<Self::Answer1, Error> {
+        Day3Input(input).get_score()
+    }
+
+    fn part2(input: &Self::Puzzle) -> Result<Self::Answer2, Error> {
+        let chunked: Day3Chunked = Day3Input(input).into();
+        chunked.get_score()
     }
 }
 
@@ -109,7 +140,7 @@ mod test {
 
         let mut scores_it = scores.iter();
         for line in TEST_DATA.lines().map(str::trim_end) {
-            let dup = find_duplicate(line);
+            let dup = find_duplicate(line).unwrap();
             let score = dup.get_score();
             println!("line: {} dup: {} score: {}", line, dup, score);
 
@@ -119,16 +150,18 @@ mod test {
 
     #[test]
     fn test_sum() {
-        let sum = Day3Input(TEST_DATA).get_score();
+        let sum = Day3Input(TEST_DATA).get_score().unwrap();
         println!("total score: {}", sum);
 
         assert_eq!(sum, 157);
+        assert!(Day3Input("abc").get_score().is_err());
+        assert!(Day3Input("ab1b").get_score().is_err());
     }
 
     #[test]
     fn test_chunked() {
         let chunked: Day3Chunked = Day3Input(TEST_DATA).into();
-        let sum = chunked.get_score();
+        let sum = chunked.get_score().unwrap();
 
         println!("chunked score: {}", sum);
         assert_eq!(sum, 70);
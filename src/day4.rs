@@ -1,7 +1,10 @@
 
 use crate::Error;
+use crate::solver::Solver;
 
-use std::{ops::RangeInclusive, str::FromStr, num::ParseIntError};
+use std::{ops::RangeInclusive, str::FromStr};
+use crate::utils::{parse_at, parse_lines};
+use crate::utils::ranges::RangeExt;
 
 
 #[derive(Debug)]
@@ -12,26 +15,18 @@ pub struct SectionAssignment(SectionRange, SectionRange);
 
 impl SectionAssignment {
 
-    pub fn find_overlap(&self) -> RangeInclusive<usize> {
+    pub fn find_overlap(&self) -> Option<RangeInclusive<usize>> {
         let SectionAssignment(SectionRange(range1), SectionRange(range2)) = &self;
-
-        let max_start = std::cmp::max(range1.start(), range2.start());
-        let min_end = std::cmp::min(range1.end(), range2.end());
-
-        *max_start..=*min_end
+        range1.intersect(range2)
     }
 
     pub fn is_work_done_twice(&self) -> bool {
         let SectionAssignment(SectionRange(range1), SectionRange(range2)) = &self;
-        let overlap = self.find_overlap();
-
-        return &overlap == range1 || &overlap == range2;
+        range1.contains_fully(range2)
     }
 
     pub fn has_overlap(&self) -> bool {
-        let overlap = self.find_overlap();
-
-        return overlap.end() >= overlap.start();
+        self.find_overlap().is_some()
     }
 
 }
@@ -43,10 +38,10 @@ impl FromStr for SectionRange {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (first, second) = s.split_once('-').ok_or(Error(format!("invalid section range: {}", s)))?;
+        let (first, second) = s.split_once('-').ok_or_else(|| Error::parse(format!("invalid section range: {}", s)))?;
 
-        let first_number = first.parse().map_err(|e: ParseIntError| Error(e.to_string()))?;
-        let second_number = second.parse().map_err(|e: ParseIntError| Error(e.to_string()))?;
+        let first_number = parse_at(s, first)?;
+        let second_number = parse_at(s, second)?;
 
         Ok(SectionRange(first_number..=second_number))
     }
@@ -56,10 +51,10 @@ impl FromStr for SectionAssignment {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (first_range, secong_range) = s.split_once(',').ok_or(Error(format!("invalid section assignment: {}", s)))?;
+        let (first_range, second_range) = s.split_once(',').ok_or_else(|| Error::parse(format!("invalid section assignment: {}", s)))?;
 
-        let first_range = first_range.parse()?;
-        let second_range = secong_range.parse()?;
+        let first_range = first_range.parse().map_err(|e: Error| e.at(s, first_range))?;
+        let second_range = second_range.parse().map_err(|e: Error| e.at(s, second_range))?;
 
         Ok(SectionAssignment(first_range, second_range))
     }
@@ -69,8 +64,29 @@ impl FromStr for SectionAssignments {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let res: Result<Vec<SectionAssignment>, Error> = s.lines().map(|s| s.parse()).collect();
-        Ok(SectionAssignments(res?))
+        Ok(SectionAssignments(parse_lines(s)?))
+    }
+}
+
+pub struct Day4;
+
+impl Solver for Day4 {
+    type Puzzle = SectionAssignments;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Puzzle, Error> {
+        input.parse()
+    }
+
+    fn part1(assignments: &Self::Puzzle) -> Result<Self::Answer1, Error> {
+        let SectionAssignments(assignments) = assignments;
+        Ok(assignments.iter().filter(|a| a.is_work_done_twice()).count())
+    }
+
+    fn part2(assignments: &Self::Puzzle) -> Result<Self::Answer2, Error> {
+        let SectionAssignments(assignments) = assignments;
+        Ok(assignments.iter().filter(|a| a.has_overlap()).count())
     }
 }
 
@@ -94,6 +110,7 @@ mod test {
         let count = assignments.iter().filter(|a| a.is_work_done_twice()).count();
 
         println!("twice work times: {}", count);
+        assert_eq!(2, count);
     }
 
     #[test]
@@ -103,7 +120,15 @@ mod test {
         let count = assignments.iter().filter(|a| a.has_overlap()).count();
 
         println!("overlapping work times: {}", count);
+        assert_eq!(4, count);
     }
 
+    #[test]
+    fn test_parse_error_location() {
+        let error = "2-4,6-8\n2-3,4-x\n".parse::<SectionAssignments>().unwrap_err();
+
+        assert_eq!(Some(2), error.line());
+        assert_eq!(Some(7), error.column());
+    }
 
 }
\ No newline at end of file
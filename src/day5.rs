@@ -3,6 +3,8 @@
 use std::str::FromStr;
 
 use crate::Error;
+use crate::solver::Solver;
+use crate::utils::parser::parse_with;
 
 #[derive(Debug, Default)]
 pub struct Instruction {
@@ -15,12 +17,16 @@ impl FromStr for Instruction {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let split: Vec<&str> = s.split(' ').collect();
-
-        match split[..] {
-            ["move", c, "from", f, "to", t] => Ok(Instruction { count: c.parse().unwrap(), from: f.parse().unwrap(), to: t.parse().unwrap() }),
-            _ => Err(Error(format!("invalid line: {}", s))),
-        }
+        parse_with(s, |p| {
+            p.literal("move")?;
+            let count = p.integer()?;
+            p.literal("from")?;
+            let from = p.integer()?;
+            p.literal("to")?;
+            let to = p.integer()?;
+
+            Ok(Instruction { count, from, to })
+        })
     }
 }
 
@@ -29,11 +35,12 @@ pub struct AllInstructions(Vec<Instruction>);
 
 impl AllInstructions {
 
-    pub fn eval<const N: usize, L: EvalLogic>(&self, stacks: &mut Stacks<N>) {
+    pub fn eval<const N: usize, L: EvalLogic>(&self, stacks: &mut Stacks<N>) -> Result<(), Error> {
         let AllInstructions(instructions) = self;
         for instruction in instructions {
-            stacks.eval::<L>(instruction);
+            stacks.eval::<L>(instruction)?;
         }
+        Ok(())
     }
 
 }
@@ -45,15 +52,15 @@ impl FromStr for AllInstructions {
 
         let mut result = Vec::new();
 
-        let mut lines = s.lines();
-        for l in &mut lines {
+        let mut lines = s.lines().enumerate();
+        for (_, l) in &mut lines {
             if l.is_empty() {
                 break;
             }
         }
 
-        for line in lines {
-            let instruction = line.trim_end().parse()?;
+        for (i, line) in lines {
+            let instruction = line.trim_end().parse().map_err(|e: Error| e.with_line(i + 1))?;
             result.push(instruction);
         }
 
@@ -62,34 +69,34 @@ impl FromStr for AllInstructions {
 }
 
 pub trait EvalLogic {
-    fn eval<const N: usize>(stacks: &mut Stacks<N>, instruction: &Instruction);
+    fn eval<const N: usize>(stacks: &mut Stacks<N>, instruction: &Instruction) -> Result<(), Error>;
 }
 
 pub struct CrateMover9000;
 pub struct CrateMover9001;
 
 impl EvalLogic for CrateMover9000 {
-    fn eval<const N: usize>(stacks: &mut Stacks<N>, instruction: &Instruction) {
-        let Stacks(stacks) = stacks;
+    fn eval<const N: usize>(stacks: &mut Stacks<N>, instruction: &Instruction) -> Result<(), Error> {
         for _ in 0..instruction.count {
-            let element = stacks[instruction.from-1].pop().unwrap();
-            stacks[instruction.to-1].push(element);
+            let element = stacks.pop(instruction.from)?;
+            stacks.push(instruction.to, element)?;
         }
+        Ok(())
     }
 }
 
 impl EvalLogic for CrateMover9001 {
-    fn eval<const N: usize>(stacks: &mut Stacks<N>, instruction: &Instruction) {
-        let Stacks(stacks) = stacks;
+    fn eval<const N: usize>(stacks: &mut Stacks<N>, instruction: &Instruction) -> Result<(), Error> {
         let mut tmp_stack = Vec::new();
         for _ in 0..instruction.count {
-            let element = stacks[instruction.from-1].pop().unwrap();
+            let element = stacks.pop(instruction.from)?;
             tmp_stack.push(element)
         }
 
         for element in tmp_stack.iter().rev() {
-            stacks[instruction.to-1].push(*element);
+            stacks.push(instruction.to, *element)?;
         }
+        Ok(())
     }
 }
 
@@ -108,15 +115,32 @@ impl<const N: usize> Stacks<N> {
 
     pub fn top_stacks(&self) -> Vec<&char> {
         let Stacks(stacks) = self;
-        stacks.iter().map(|v| &v[v.len()-1]).collect()
+        stacks.iter().filter_map(|v| v.last()).collect()
     }
 
     pub fn top_stacks_str(&self) -> String {
         self.top_stacks().iter().map(|c| *c).collect()
     }
 
-    pub fn eval<L: EvalLogic>(&mut self, instruction: &Instruction) {
-        L::eval(self, instruction);
+    fn get_stack_mut(&mut self, stack_number: usize) -> Result<&mut Vec<char>, Error> {
+        let Stacks(stacks) = self;
+        stack_number.checked_sub(1)
+            .and_then(|i| stacks.get_mut(i))
+            .ok_or_else(|| Error::invalid_state(format!("invalid stack: {}", stack_number)))
+    }
+
+    pub fn pop(&mut self, stack_number: usize) -> Result<char, Error> {
+        self.get_stack_mut(stack_number)?.pop()
+            .ok_or_else(|| Error::invalid_state(format!("stack {} is empty", stack_number)))
+    }
+
+    pub fn push(&mut self, stack_number: usize, element: char) -> Result<(), Error> {
+        self.get_stack_mut(stack_number)?.push(element);
+        Ok(())
+    }
+
+    pub fn eval<L: EvalLogic>(&mut self, instruction: &Instruction) -> Result<(), Error> {
+        L::eval(self, instruction)
     }
 }
 
@@ -150,6 +174,30 @@ impl<const N: usize> FromStr for Stacks<N> {
     }
 }
 
+pub struct Day5;
+
+impl Solver for Day5 {
+    type Puzzle = (Stacks<9>, AllInstructions);
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Puzzle, Error> {
+        Ok((input.parse()?, input.parse()?))
+    }
+
+    fn part1((stacks, instructions): &Self::Puzzle) -> Result<Self::Answer1, Error> {
+        let mut stacks = stacks.clone();
+        instructions.eval::<9, CrateMover9000>(&mut stacks)?;
+        Ok(stacks.top_stacks_str())
+    }
+
+    fn part2((stacks, instructions): &Self::Puzzle) -> Result<Self::Answer2, Error> {
+        let mut stacks = stacks.clone();
+        instructions.eval::<9, CrateMover9001>(&mut stacks)?;
+        Ok(stacks.top_stacks_str())
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -169,6 +217,9 @@ mod test {
         let instructions: AllInstructions = INPUT_TEXT.parse().unwrap();
 
         println!("{:?}", instructions);
+
+        let error = "    [D]\n 1   2\n\nmove 1 from 2 to 1\nmove x from 1 to 3\n".parse::<AllInstructions>().unwrap_err();
+        assert_eq!("line 5, column 6: cannot parse x: invalid digit found in string", error.to_string());
     }
 
     #[test]
@@ -177,11 +228,16 @@ mod test {
         let instructions: AllInstructions = INPUT_TEXT.parse().unwrap();
 
         for instr in &instructions.0 {
-            stacks.eval::<CrateMover9000>(instr);
+            stacks.eval::<CrateMover9000>(instr).unwrap();
         }
 
         let top_stacks = stacks.top_stacks_str();
         println!("{}", top_stacks);
+        assert_eq!("CMZ", top_stacks);
+
+        let instruction = Instruction { count: 5, from: 1, to: 2 };
+        assert!(stacks.eval::<CrateMover9000>(&instruction).is_err());
+        assert!(stacks.eval::<CrateMover9000>(&Instruction { count: 1, from: 0, to: 2 }).is_err());
     }
 
     #[test]
@@ -189,7 +245,7 @@ mod test {
         let mut stacks: Stacks<3> = INPUT_TEXT.parse().unwrap();
         let instructions: AllInstructions = INPUT_TEXT.parse().unwrap();
 
-        instructions.eval::<3, CrateMover9001>(&mut stacks);
+        instructions.eval::<3, CrateMover9001>(&mut stacks).unwrap();
 
         let top_stacks = stacks.top_stacks_str();
         println!("{}", top_stacks);
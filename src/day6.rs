@@ -1,20 +1,19 @@
+use crate::Error;
+use crate::solver::Solver;
+use crate::utils::ringbuf::RingBuf;
 
 #[derive(Debug)]
 pub struct CodeDetector<const N: usize> {
-  ring_buf: [u8; N],
+  ring_buf: RingBuf<u8>,
   counters: [usize; 26],
-  cur: usize,
-  len: usize,
 }
 
 impl<const N: usize> Default for CodeDetector<N> {
   
   fn default() -> Self {
     CodeDetector {
-      ring_buf: [0; N],
+      ring_buf: RingBuf::new(N),
       counters: [0; 26],
-      cur: 0,
-      len: 0,
     }
   }
 
@@ -25,25 +24,14 @@ impl<const N: usize> CodeDetector<N> {
   pub fn feed(&mut self, c: char) {
     let c = (c as u8) - ('a' as u8);
 
-    if self.len < N {
-      self.ring_buf[self.len] = c;
-      self.counters[c as usize] += 1;
-      self.len += 1;
-      return;
+    if let Some(evicted) = self.ring_buf.push(c) {
+      self.counters[evicted as usize] -= 1;
     }
-
-    let evicted = self.ring_buf[self.cur];
-    self.counters[evicted as usize] -= 1;
-
-    self.ring_buf[self.cur] = c;
     self.counters[c as usize] += 1;
-
-    self.cur += 1;
-    self.cur %= N;
   }
 
   pub fn all_unique(&self) -> Option<bool> {
-    if self.len < N {
+    if self.ring_buf.len() < N {
       None
     } else {
       Some(!self.ring_buf.iter().map(|e| self.counters[*e as usize]).any(|e| e > 1))
@@ -67,6 +55,33 @@ pub fn find_sync_start<const N: usize>(s: &str) -> Option<usize> {
     return None;
 }
 
+pub struct Day6;
+
+impl Solver for Day6 {
+    type Puzzle = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Puzzle, Error> {
+        let input = input.trim_end();
+
+        // the detector only counts lowercase letters
+        if let Some((i, c)) = input.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+            return Err(Error::cannot_parse_at(input, &input[i..i + c.len_utf8()]));
+        }
+
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Puzzle) -> Result<Self::Answer1, Error> {
+        find_sync_start::<4>(input).ok_or_else(|| Error::invalid_state("no sync start found"))
+    }
+
+    fn part2(input: &Self::Puzzle) -> Result<Self::Answer2, Error> {
+        find_sync_start::<14>(input).ok_or_else(|| Error::invalid_state("no message start found"))
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -122,6 +137,13 @@ mod test {
       assert_eq!(Some(26), find_sync_start::<14>(test_4));
   }
 
+  #[test]
+  fn test_parse() {
+      assert_eq!("abc", Day6::parse("abc\n").unwrap());
+      assert_eq!("column 3: cannot parse A", Day6::parse("abA").unwrap_err().to_string());
+      assert!(Day6::parse("#").is_err());
+  }
+
 
 }
 
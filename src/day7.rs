@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 use std::str::FromStr;
 use crate::Error;
+use crate::solver::Solver;
+use crate::utils::{parse_at, parse_lines};
 
 #[derive(Debug)]
 pub enum Tree {
@@ -58,23 +60,25 @@ impl DirEnt {
         }
     }
 
-    pub fn mkdir(&mut self, name: &str) -> &mut DirEnt {
+    pub fn mkdir(&mut self, name: &str) -> Result<&mut DirEnt, Error> {
         match &mut self.content {
-            Tree::File(_) => panic!("not a dir"),
+            Tree::File(_) => Err(Error::invalid_state(format!("not a dir: {}", self.name))),
             Tree::Dir(content) => {
                 let dir_ent = DirEnt::dir(name, Vec::new());
+                let index = content.len();
                 content.push(dir_ent);
-                content.last_mut().unwrap()
+                Ok(&mut content[index])
             }
         }
     }
 
-    pub fn push_file(&mut self, name: &str, size: usize) {
+    pub fn push_file(&mut self, name: &str, size: usize) -> Result<(), Error> {
         match &mut self.content {
-            Tree::File(_) => panic!("not a dir"),
+            Tree::File(_) => Err(Error::invalid_state(format!("not a dir: {}", self.name))),
             Tree::Dir(content) => {
                 let dir_ent = DirEnt::file(name, size);
                 content.push(dir_ent);
+                Ok(())
             }
         }
     }
@@ -111,11 +115,7 @@ impl DirEnt {
         let mut cur = self;
 
         for bit in bits {
-            let resolve = cur.content.find_dir_ent_name(bit.as_ref());
-            if let None = resolve {
-                return None;
-            }
-            cur = resolve.unwrap();
+            cur = cur.content.find_dir_ent_name(bit.as_ref())?;
         }
 
         Some(cur)
@@ -125,11 +125,7 @@ impl DirEnt {
         let mut cur = self;
 
         for bit in bits {
-            let resolve = cur.content.find_dir_ent_name_mut(bit.as_ref());
-            if let None = resolve {
-                return None;
-            }
-            cur = resolve.unwrap();
+            cur = cur.content.find_dir_ent_name_mut(bit.as_ref())?;
         }
 
         Some(cur)
@@ -148,7 +144,7 @@ impl FromStr for Command {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (first, second) = s.split_once(" ").ok_or(Error(format!("invalid command: {}", s)))?;
+        let (first, second) = s.split_once(" ").ok_or_else(|| Error::parse(format!("invalid command: {}", s)))?;
 
         let command = match (first, second) {
             ("$", cmd) => {
@@ -158,7 +154,7 @@ impl FromStr for Command {
                 }
             }
             ("dir", dir_name) => Command::Dir(dir_name.to_string()),
-            (size, file_name) => Command::File(file_name.to_string(), size.parse().unwrap()),
+            (size, file_name) => Command::File(file_name.to_string(), parse_at(s, size)?),
         };
 
         Ok(command)
@@ -189,33 +185,78 @@ impl<'a> Environment<'a> {
             self.cur_path.clear();
             return Ok(())
         }
-        let cur_dir_ent = self.get_cur_dir_ent();
+        let cur_dir_ent = self.get_cur_dir_ent()?;
         if cur_dir_ent.resolve(&[dir]).is_none() {
-            return Err(Error(format!("invalid directory: {}", dir)));
+            return Err(Error::invalid_state(format!("invalid directory: {}", dir)));
         }
         self.cur_path.push(dir.to_string());
         Ok(())
     }
 
-    pub fn get_cur_dir_ent(&self) -> &DirEnt {
-        self.root_dir_ent.resolve(&self.cur_path[..]).unwrap()
+    pub fn get_cur_dir_ent(&self) -> Result<&DirEnt, Error> {
+        self.root_dir_ent.resolve(&self.cur_path[..])
+            .ok_or_else(|| Error::invalid_state(format!("cannot resolve /{}", self.cur_path.join("/"))))
     }
 
-    pub fn get_cur_dir_ent_mut(&mut self) -> &mut DirEnt {
-        self.root_dir_ent.resolve_mut(&self.cur_path[..]).unwrap()
+    pub fn get_cur_dir_ent_mut(&mut self) -> Result<&mut DirEnt, Error> {
+        let cur_path = &self.cur_path;
+        self.root_dir_ent.resolve_mut(&cur_path[..])
+            .ok_or_else(|| Error::invalid_state(format!("cannot resolve /{}", cur_path.join("/"))))
     }
 
-    pub fn eval(&mut self, cmd: &Command) {
+    pub fn eval(&mut self, cmd: &Command) -> Result<(), Error> {
         match cmd {
-            Command::Cd(dir) => {self.cd(dir).unwrap();},
-            Command::Dir(name) => {self.get_cur_dir_ent_mut().mkdir(name);},
-            Command::File(name, size) => {self.get_cur_dir_ent_mut().push_file(name, *size);},
+            Command::Cd(dir) => self.cd(dir)?,
+            Command::Dir(name) => {self.get_cur_dir_ent_mut()?.mkdir(name)?;},
+            Command::File(name, size) => self.get_cur_dir_ent_mut()?.push_file(name, *size)?,
             _ => {}
         };
+        Ok(())
     }
 
 }
 
+pub struct Day7;
+
+impl Solver for Day7 {
+    type Puzzle = HashMap<String, usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Puzzle, Error> {
+        let commands: Vec<Command> = parse_lines(input)?;
+
+        let mut root = DirEnt::empty_dir("/");
+        {
+            let mut env = Environment::new(&mut root);
+            for cmd in commands {
+                env.eval(&cmd)?;
+            }
+        }
+
+        Ok(root.du_by_dir())
+    }
+
+    fn part1(du_by_dir: &Self::Puzzle) -> Result<Self::Answer1, Error> {
+        Ok(du_by_dir.values().filter(|v| **v <= 100_000).sum())
+    }
+
+    fn part2(du_by_dir: &Self::Puzzle) -> Result<Self::Answer2, Error> {
+        const DISK_SPACE: usize = 70_000_000;
+        const NEEDED_SPACE: usize = 30_000_000;
+
+        let complete_usage = du_by_dir.get("/").ok_or_else(|| Error::invalid_state("missing root directory"))?;
+        let unused_space = DISK_SPACE.checked_sub(*complete_usage).ok_or_else(|| Error::invalid_state("disk is over capacity"))?;
+        let cleanup_space = NEEDED_SPACE.saturating_sub(unused_space);
+
+        du_by_dir.values()
+            .filter(|size| **size >= cleanup_space)
+            .min()
+            .copied()
+            .ok_or_else(|| Error::invalid_state("no directory is large enough"))
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -246,19 +287,20 @@ mod test {
         let mut env = Environment::new(&mut root);
 
         env.cd("/").unwrap();
-        env.get_cur_dir_ent_mut().push_file("b.txt", 14848514);
-        env.get_cur_dir_ent_mut().push_file("c.dat", 8504156);
+        env.get_cur_dir_ent_mut().unwrap().push_file("b.txt", 14848514).unwrap();
+        env.get_cur_dir_ent_mut().unwrap().push_file("c.dat", 8504156).unwrap();
 
-        env.get_cur_dir_ent_mut().mkdir("a");
-        env.get_cur_dir_ent_mut().mkdir("d");
+        env.get_cur_dir_ent_mut().unwrap().mkdir("a").unwrap();
+        env.get_cur_dir_ent_mut().unwrap().mkdir("d").unwrap();
         env.cd("a").unwrap();
-        env.get_cur_dir_ent_mut().mkdir("e");
+        env.get_cur_dir_ent_mut().unwrap().mkdir("e").unwrap();
         env.cd("e").unwrap();
-        env.get_cur_dir_ent_mut().push_file("i", 584);
+        env.get_cur_dir_ent_mut().unwrap().push_file("i", 584).unwrap();
         env.cd("..").unwrap();
         env.cd("..").unwrap();
         env.cd("d").unwrap();
-        env.get_cur_dir_ent_mut().push_file("j", 4060174);
+        env.get_cur_dir_ent_mut().unwrap().push_file("j", 4060174).unwrap();
+        assert!(env.eval(&Command::Cd("missing".to_string())).is_err());
 
         println!("{:#?}", root);
     }
@@ -268,22 +310,22 @@ mod test {
 
         let mut r = DirEnt::empty_dir("/");
 
-        let r_a = r.mkdir("a");
-        let r_a_e = r_a.mkdir("e");
-        r_a_e.push_file("i", 584);
+        let r_a = r.mkdir("a").unwrap();
+        let r_a_e = r_a.mkdir("e").unwrap();
+        r_a_e.push_file("i", 584).unwrap();
 
-        r_a.push_file("f", 29116);
-        r_a.push_file("g", 2557);
-        r_a.push_file("h.lst", 62596);
+        r_a.push_file("f", 29116).unwrap();
+        r_a.push_file("g", 2557).unwrap();
+        r_a.push_file("h.lst", 62596).unwrap();
 
-        r.push_file("b.txt", 14848514);
-        r.push_file("c.dat", 8504156);
+        r.push_file("b.txt", 14848514).unwrap();
+        r.push_file("c.dat", 8504156).unwrap();
 
-        let r_d = r.mkdir("d");
-        r_d.push_file("j", 4060174);
-        r_d.push_file("d.log", 8033020);
-        r_d.push_file("d.ext", 5626152);
-        r_d.push_file("k", 7214296);
+        let r_d = r.mkdir("d").unwrap();
+        r_d.push_file("j", 4060174).unwrap();
+        r_d.push_file("d.log", 8033020).unwrap();
+        r_d.push_file("d.ext", 5626152).unwrap();
+        r_d.push_file("k", 7214296).unwrap();
 
         println!("{:#?}", r);
 
@@ -307,7 +349,7 @@ mod test {
         let mut env = Environment::new(&mut root);
 
         for cmd in commands {
-            env.eval(&cmd);
+            env.eval(&cmd).unwrap();
         }
 
         let du_by_dir = root.du_by_dir();
@@ -1,104 +1,47 @@
-
 use std::str::FromStr;
 use std::fmt::{Display, Formatter};
 
 use crate::Error;
+use crate::solver::Solver;
+use crate::utils::grid::Grid;
+use crate::utils::vec2::Vec2;
 
 #[derive(Debug)]
-pub struct Field<const N: usize> {
-  trees: [usize; N],
-}
-
-impl<const N: usize> Field<N> {
-
-  pub fn side_length() -> usize {
-    (N as f32).sqrt() as usize
+pub struct Field(Grid<usize>);
+
+// counts the trees seen along a line of sight until the view is blocked
+fn viewing_distance<'a>(height: usize, line_of_sight: impl Iterator<Item=&'a usize>) -> usize {
+  let mut distance = 0;
+  for tree in line_of_sight {
+    distance += 1;
+    if *tree >= height {
+      break;
+    }
   }
+  distance
+}
 
-  pub fn xy_to_idx(&self, x: usize, y: usize) -> usize {
-    let side_length = Self::side_length();
-    assert!(x < side_length);
-    assert!(y < side_length);
-
-    y * side_length + x
-  }
+fn is_visible_along<'a>(height: usize, mut line_of_sight: impl Iterator<Item=&'a usize>) -> bool {
+  line_of_sight.all(|tree| *tree < height)
+}
 
-  pub fn get(&self, x: usize, y: usize) -> &usize {
-    let index = self.xy_to_idx(x, y);
-    &self.trees[index]
-  }
+impl Field {
 
-  pub fn set(&mut self, x: usize, y: usize, value: usize) {
-    let index = self.xy_to_idx(x, y);
-    self.trees[index] = value;
+  pub fn get(&self, x: usize, y: usize) -> Option<&usize> {
+    let Field(trees) = self;
+    trees.get(&Vec2(x, y))
   }
 
   pub fn is_visible(&self, x: usize, y: usize) -> bool {
-    let side_length = Self::side_length();
-
-    let cur_tree_height = self.get(x, y);
-
-    if x == 0 || x == side_length - 1 || y == 0 || y == side_length -1 {
-      return true;
-    } else {
-      let mut visibility_check = true;
-
-      // left
-      for check_x in 0..x {
-        let other_tree = self.get(check_x, y);
-        if other_tree >= cur_tree_height {
-          visibility_check = false;
-          break;
-        }
-      }
-
-      if visibility_check {
-        return true;
-      }
-
-      visibility_check = true;
-
-      // right
-      for check_x in x+1..side_length {
-        let other_tree = self.get(check_x, y);
-        if other_tree >= cur_tree_height {
-          visibility_check = false;
-          break;
-        }
-      }
-
-      if visibility_check {
-        return true;
-      }
-
-      visibility_check = true;
-
-      // up
-      for check_y in 0..y {
-        let other_tree = self.get(x, check_y);
-        if other_tree >= cur_tree_height {
-          visibility_check = false;
-          break;
-        }
-      }
-
-      if visibility_check {
-        return true;
-      }
-
-      visibility_check = true;
-
-      // down
-      for check_y in y+1..side_length {
-        let other_tree = self.get(x, check_y);
-        if other_tree >= cur_tree_height {
-          visibility_check = false;
-          break;
-        }
-      }
-
-      return visibility_check;
-    }
+    let Field(trees) = self;
+    let (Some(height), Some(row)) = (self.get(x, y), trees.row(y)) else {
+      return false;
+    };
+
+    is_visible_along(*height, row[..x].iter().rev())
+      || is_visible_along(*height, row[x + 1..].iter())
+      || is_visible_along(*height, trees.column(x).take(y).rev())
+      || is_visible_along(*height, trees.column(x).skip(y + 1))
   }
 
 /*
@@ -113,130 +56,91 @@ impl<const N: usize> Field<N> {
 */
 
   pub fn get_visibility_score(&self, x: usize, y: usize) -> usize {
-    let side_length = Self::side_length();
-
-    if x == 0 || x == side_length - 1 || y == 0 || y == side_length -1 {
+    let Field(trees) = self;
+    let (Some(height), Some(row)) = (self.get(x, y), trees.row(y)) else {
       return 0;
-    }
-
-    let cur_tree_height = self.get(x, y);
-
-    let mut left_score: usize = 0;
-    let mut right_score: usize = 0;
-    let mut up_score: usize = 0;
-    let mut down_score: usize = 0;
-
-    // left
-    for d_x in 1..=x as isize {
-      let check_tree = self.get((x as isize - d_x) as usize, y);
-      left_score += 1;
-      if check_tree >= cur_tree_height {
-        break;
-      }
-    }
-
-    // right
-    for d_x in 1..(side_length - x) as isize {
-      let check_tree = self.get((x as isize + d_x) as usize, y);
-      right_score += 1;
-      if check_tree >= cur_tree_height {
-        break;
-      }
-    }
-
-    // up
-    for d_y in 1..=y as isize {
-      let check_tree = self.get(x, (y as isize - d_y) as usize);
-      up_score += 1;
-      if check_tree >= cur_tree_height {
-        break;
-      }
-    }
-
-    // down
-    for d_y in 1..(side_length - y) as isize {
-      let check_tree = self.get(x, (y as isize + d_y) as usize);
-      down_score += 1;
-      if check_tree >= cur_tree_height {
-        break;
-      }
-    }
+    };
 
-    left_score * right_score * down_score * up_score
+    viewing_distance(*height, row[..x].iter().rev())
+      * viewing_distance(*height, row[x + 1..].iter())
+      * viewing_distance(*height, trees.column(x).take(y).rev())
+      * viewing_distance(*height, trees.column(x).skip(y + 1))
   }
 
-  pub fn to_visibility_field(&self) -> Field<N> {
-    let side_length = Self::side_length();
-    let mut trees: [usize; N] = [0; N];
-    let mut i: usize = 0;
+  pub fn to_visibility_field(&self) -> Field {
+    let Field(trees) = self;
+    let mut visibility = trees.map(|_| 0);
 
-    for y in 0..side_length {
-      for x in 0..side_length {
-        trees[i] = self.is_visible(x, y) as usize;
-        i += 1;
-      }
+    for pos in trees.positions() {
+      let Vec2(x, y) = pos;
+      visibility[&pos] = self.is_visible(x, y) as usize;
     }
 
-    Field { trees }
+    Field(visibility)
   }
 
-  pub fn to_score_field(&self) -> Field<N> {
-    let side_length = Self::side_length();
-    let mut trees: [usize; N] = [0; N];
-    let mut i: usize = 0;
+  pub fn to_score_field(&self) -> Field {
+    let Field(trees) = self;
+    let mut scores = trees.map(|_| 0);
 
-    for y in 0..side_length {
-      for x in 0..side_length {
-        trees[i] = self.get_visibility_score(x, y);
-        i += 1;
-      }
+    for pos in trees.positions() {
+      let Vec2(x, y) = pos;
+      scores[&pos] = self.get_visibility_score(x, y);
     }
 
-    Field { trees }
+    Field(scores)
   }
 
   pub fn count_non_zero(&self) -> usize {
-    self.trees.iter().filter(|t| **t != 0).count()
+    let Field(trees) = self;
+    trees.iter().filter(|(_, t)| **t != 0).count()
   }
 
   pub fn max(&self) -> Option<&usize> {
-    self.trees.iter().max()
+    let Field(trees) = self;
+    trees.iter().map(|(_, t)| t).max()
   }
 }
 
-impl<const N: usize> FromStr for Field<N> {
+impl FromStr for Field {
   type Err = Error;
 
-  fn from_str(s: &str) -> Result<Self, Self::Err> { 
-    let mut trees: [usize; N] = [0; N];
-    let mut i: usize = 0;
-
-    for line in s.lines() {
-      for c in line.trim_end().chars() {
-        let value = (c as u8) - ('0' as u8);
-        if value >= 10 {
-          return Err(Error(format!("invalid tree: {}", c)));
-        }
-        trees[i] = value as usize;
-        i += 1;
-      }
-    }
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let trees = Grid::parse_with(s, |_, c| {
+      c.to_digit(10)
+        .map(|value| value as usize)
+        .ok_or_else(|| Error::parse(format!("invalid tree: {}", c)))
+    })?;
 
-    Ok(Field { trees })
+    Ok(Field(trees))
   }
 
 }
 
-impl<const N: usize> Display for Field<N> {
+impl Display for Field {
   fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
-    let side_length = Self::side_length();
-    for y in 0..side_length {
-      for x in 0..side_length {
-        write!(f, "{}", self.get(x, y))?;
-      }
-      write!(f, "\n")?;
-    }
-    Ok(())
+    let Field(trees) = self;
+    write!(f, "{}", trees)
+  }
+}
+
+pub struct Day8;
+
+impl Solver for Day8 {
+  type Puzzle = Field;
+  type Answer1 = usize;
+  type Answer2 = usize;
+
+  fn parse(input: &str) -> Result<Self::Puzzle, Error> {
+    input.parse()
+  }
+
+  fn part1(field: &Self::Puzzle) -> Result<Self::Answer1, Error> {
+    Ok(field.to_visibility_field().count_non_zero())
+  }
+
+  fn part2(field: &Self::Puzzle) -> Result<Self::Answer2, Error> {
+    field.to_score_field().max().copied().ok_or_else(|| Error::invalid_state("empty field"))
   }
 }
 
@@ -250,36 +154,37 @@ mod test {
   #[test]
   fn test_parse_field() {
 
-    let field: Field<{5*5}> = INPUT.parse().unwrap();
+    let field: Field = INPUT.parse().unwrap();
 
     println!("{:#?}", field);
     println!("{}", field);
 
+    assert!("303\n25\n".parse::<Field>().is_err());
+    assert_eq!("line 1, column 2: invalid tree: x", "3x3\n".parse::<Field>().unwrap_err().to_string());
+    assert_eq!(None, field.get(5, 0));
   }
 
   #[test]
   fn test_to_viz_map() {
 
-    let field: Field<{5*5}> = INPUT.parse().unwrap();
+    let field: Field = INPUT.parse().unwrap();
     let viz_field = field.to_visibility_field();
 
     println!("{}", field);
     println!("{}", viz_field);
 
-    println!("visible: {}", viz_field.count_non_zero());
-
+    assert_eq!(21, viz_field.count_non_zero());
   }
 
   #[test]
   fn test_to_score_map() {
 
-    let field: Field<{5*5}> = INPUT.parse().unwrap();
+    let field: Field = INPUT.parse().unwrap();
     let score_field = field.to_score_field();
 
     println!("{}", field);
     println!("{:#?}", score_field);
-    println!("{:?}", score_field.max());
+    assert_eq!(Some(&8), score_field.max());
   }
 
 }
-
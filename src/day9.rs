@@ -1,45 +1,19 @@
 use std::collections::HashSet;
 use std::str::FromStr;
 use crate::Error;
+use crate::solver::Solver;
+use crate::utils::{parse_at, parse_lines};
+use crate::utils::vec2::Vec2;
 
-#[derive(Debug, Eq, PartialEq, Hash, Default, Clone)]
-pub struct Vec2(i32, i32);
+pub type Pos = Vec2<i32>;
 
-impl Vec2 {
-
-    pub fn move_mut(&mut self, delta: &Vec2) {
-        let Vec2(x,y) = self;
-        let Vec2(dx, dy) = delta;
-
-        *x += dx;
-        *y += dy;
-    }
-
-    pub fn dist_sq(&self, other: &Vec2) -> u32 {
-        let Vec2(x1,y1) = self;
-        let Vec2(x2, y2) = other;
-
-        let dx = x1 - x2;
-        let dy = y1 - y2;
-
-        (dx * dx + dy * dy) as u32
-    }
-
-    pub fn normalize(&mut self) {
-        let Vec2(x,y) = self;
-        *x = x.signum();
-        *y = y.signum();
-    }
-
-    pub fn move_towards(&mut self, target: &Vec2) {
-        let Vec2(x1,y1) = self;
-        let Vec2(x2, y2) = target;
-
-        let mut delta = Vec2(*x2-*x1, *y2-*y1);
-        delta. normalize();
-        self.move_mut(&delta);
+fn is_touching(a: &Pos, b: &Pos) -> bool {
+    a.clone().chebyshev_dist(b.clone()) <= 1
+}
 
-    }
+fn move_towards(pos: &mut Pos, target: &Pos) {
+    let delta = (target.clone() - pos.clone()).signum();
+    *pos += delta;
 }
 
 
@@ -60,12 +34,12 @@ impl FromStr for Dir {
             "L" => Ok(Dir::Left),
             "U" => Ok(Dir::Up),
             "D" => Ok(Dir::Down),
-            _ => Err(Error(format!("invalid dir: {}", s))),
+            _ => Err(Error::parse(format!("invalid dir: {}", s))),
         }
     }
 }
 
-impl From<&Dir> for Vec2 {
+impl From<&Dir> for Pos {
     fn from(cmd: &Dir) -> Self {
         match cmd {
             Dir::Left => Vec2(-1, 0),
@@ -86,29 +60,29 @@ impl FromStr for Command {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (dir, steps) = s.split_once(" ").ok_or(Error(format!("invalid line: {}", s)))?;
+        let (dir, steps) = s.split_once(" ").ok_or_else(|| Error::parse(format!("invalid line: {}", s)))?;
 
-        let dir: Dir = dir.parse()?;
-        let steps: u32 = steps.parse()?;
+        let dir: Dir = dir.parse().map_err(|e: Error| e.at(s, dir))?;
+        let steps: u32 = parse_at(s, steps)?;
 
         Ok(Command { dir, steps })
     }
 }
 
 pub fn apply_commands(commands: &Vec<Command>) -> usize {
-    let mut head = Vec2::default();
-    let mut tail = Vec2::default();
-    let mut position_collector: HashSet<Vec2> = HashSet::new();
+    let mut head = Pos::default();
+    let mut tail = Pos::default();
+    let mut position_collector: HashSet<Pos> = HashSet::new();
 
     position_collector.insert(tail.clone());
 
     for cmd in commands {
-        let dir_vector: Vec2 = (&cmd.dir).into();
+        let dir_vector: Pos = (&cmd.dir).into();
 
         for _ in 0..cmd.steps {
             let head_prev = head.clone();
-            head.move_mut(&dir_vector);
-            if head.dist_sq(&tail) > 2 {
+            head += dir_vector.clone();
+            if !is_touching(&head, &tail) {
                 tail = head_prev;
                 position_collector.insert(tail.clone());
             }
@@ -119,13 +93,13 @@ pub fn apply_commands(commands: &Vec<Command>) -> usize {
 }
 
 pub fn apply_commands_10fold(commands: &Vec<Command>) -> usize {
-    let mut head = Vec2::default();
-    let mut tails: [Vec2; 9] = Default::default();
-    let mut position_collector: HashSet<Vec2> = HashSet::new();
+    let mut head = Pos::default();
+    let mut tails: [Pos; 9] = Default::default();
+    let mut position_collector: HashSet<Pos> = HashSet::new();
 
     for cmd in commands {
 
-        let dir_vector: Vec2 = (&cmd.dir).into();
+        let dir_vector: Pos = (&cmd.dir).into();
 
         for _ in 0..cmd.steps {
 
@@ -163,13 +137,13 @@ pub fn apply_commands_10fold(commands: &Vec<Command>) -> usize {
             println!();
             */
 
-            head.move_mut(&dir_vector);
+            head += dir_vector.clone();
 
             let mut prev_head = &head;
 
             for tail in tails.iter_mut() {
-                if prev_head.dist_sq(tail) > 2 {
-                    tail.move_towards(prev_head);
+                if !is_touching(prev_head, tail) {
+                    move_towards(tail, prev_head);
                 }
                 prev_head = tail;
             }
@@ -180,6 +154,26 @@ pub fn apply_commands_10fold(commands: &Vec<Command>) -> usize {
     position_collector.len()
 }
 
+pub struct Day9;
+
+impl Solver for Day9 {
+    type Puzzle = Vec<Command>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Puzzle, Error> {
+        parse_lines(input)
+    }
+
+    fn part1(commands: &Self::Puzzle) -> Result<Self::Answer1, Error> {
+        Ok(apply_commands(commands))
+    }
+
+    fn part2(commands: &Self::Puzzle) -> Result<Self::Answer2, Error> {
+        Ok(apply_commands_10fold(commands))
+    }
+}
+
 #[cfg(test)]
 mod test {
 
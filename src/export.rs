@@ -0,0 +1,383 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+use crate::bench::Stats;
+use advent_of_code_2022::utils::Error;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Format {
+    Json,
+    Csv,
+}
+
+impl Format {
+
+    pub fn from_path(path: &Path) -> Result<Format, Error> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Ok(Format::Json),
+            Some("csv") => Ok(Format::Csv),
+            _ => Err(Error::parse(format!("unknown export format, expected a .json or .csv file: {}", path.display()))),
+        }
+    }
+
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Record {
+    pub day: usize,
+    pub phase: String,
+    pub stats: Stats,
+}
+
+const CSV_HEADER: &str = "day,phase,samples,min_ns,median_ns,mean_ns,stddev_ns";
+
+fn nanos(duration: &Duration) -> u128 {
+    duration.as_nanos()
+}
+
+fn from_nanos(nanos: u64) -> Duration {
+    Duration::from_nanos(nanos)
+}
+
+pub fn to_csv(records: &[Record]) -> String {
+    let mut out = String::new();
+    writeln!(out, "{}", CSV_HEADER).unwrap();
+
+    for Record { day, phase, stats } in records {
+        writeln!(out, "{},{},{},{},{},{},{}", day, phase, stats.samples,
+                 nanos(&stats.min), nanos(&stats.median), nanos(&stats.mean), nanos(&stats.stddev)).unwrap();
+    }
+
+    out
+}
+
+pub fn from_csv(s: &str) -> Result<Vec<Record>, Error> {
+    let mut lines = s.lines().map(str::trim).filter(|l| !l.is_empty());
+
+    if lines.next() != Some(CSV_HEADER) {
+        return Err(Error::parse("missing csv header"));
+    }
+
+    lines.map(|line| {
+        let fields: Vec<&str> = line.split(',').collect();
+
+        if let [day, phase, samples, min, median, mean, stddev] = fields[..] {
+            Ok(Record {
+                day: day.parse()?,
+                phase: phase.to_string(),
+                stats: Stats {
+                    samples: samples.parse()?,
+                    min: from_nanos(min.parse()?),
+                    median: from_nanos(median.parse()?),
+                    mean: from_nanos(mean.parse()?),
+                    stddev: from_nanos(stddev.parse()?),
+                },
+            })
+        } else {
+            Err(Error::cannot_parse(line))
+        }
+    }).collect()
+}
+
+pub fn to_json(records: &[Record]) -> String {
+    let mut out = String::from("[\n");
+
+    for (i, Record { day, phase, stats }) in records.iter().enumerate() {
+        let separator = if i + 1 < records.len() { "," } else { "" };
+        writeln!(out, "  {{\"day\": {}, \"phase\": \"{}\", \"samples\": {}, \"min_ns\": {}, \"median_ns\": {}, \"mean_ns\": {}, \"stddev_ns\": {}}}{}",
+                 day, phase, stats.samples,
+                 nanos(&stats.min), nanos(&stats.median), nanos(&stats.mean), nanos(&stats.stddev),
+                 separator).unwrap();
+    }
+
+    out.push_str("]\n");
+    out
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum JsonValue {
+    Number(u64),
+    String(String),
+}
+
+struct JsonReader<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl<'a> JsonReader<'a> {
+
+    fn new(s: &'a str) -> JsonReader<'a> {
+        JsonReader { chars: s.chars().peekable() }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.peek().copied()
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), Error> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.chars.next();
+                Ok(())
+            }
+            Some(c) => Err(Error::parse(format!("expected '{}' but got '{}'", expected, c))),
+            None => Err(Error::parse(format!("expected '{}' but got end of input", expected))),
+        }
+    }
+
+    fn read_string(&mut self) -> Result<String, Error> {
+        self.expect('"')?;
+        let mut result = String::new();
+
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(result),
+                Some('\\') => match self.chars.next() {
+                    Some(c) => result.push(c),
+                    None => return Err(Error::parse("unterminated string")),
+                },
+                Some(c) => result.push(c),
+                None => return Err(Error::parse("unterminated string")),
+            }
+        }
+    }
+
+    fn read_value(&mut self) -> Result<JsonValue, Error> {
+        match self.peek() {
+            Some('"') => Ok(JsonValue::String(self.read_string()?)),
+            Some(c) if c.is_ascii_digit() => {
+                let mut digits = String::new();
+                while let Some(c) = self.chars.next_if(char::is_ascii_digit) {
+                    digits.push(c);
+                }
+                Ok(JsonValue::Number(digits.parse()?))
+            }
+            Some(c) => Err(Error::parse(format!("unexpected '{}'", c))),
+            None => Err(Error::parse("unexpected end of input")),
+        }
+    }
+
+    fn read_object(&mut self) -> Result<BTreeMap<String, JsonValue>, Error> {
+        let mut object = BTreeMap::new();
+        self.expect('{')?;
+
+        if self.peek() == Some('}') {
+            self.chars.next();
+            return Ok(object);
+        }
+
+        loop {
+            let key = self.read_string()?;
+            self.expect(':')?;
+            object.insert(key, self.read_value()?);
+
+            match self.peek() {
+                Some(',') => self.expect(',')?,
+                _ => break,
+            }
+        }
+
+        self.expect('}')?;
+        Ok(object)
+    }
+
+    fn read_objects(&mut self) -> Result<Vec<BTreeMap<String, JsonValue>>, Error> {
+        let mut objects = Vec::new();
+        self.expect('[')?;
+
+        if self.peek() == Some(']') {
+            self.chars.next();
+            return Ok(objects);
+        }
+
+        loop {
+            objects.push(self.read_object()?);
+
+            match self.peek() {
+                Some(',') => self.expect(',')?,
+                _ => break,
+            }
+        }
+
+        self.expect(']')?;
+
+        match self.peek() {
+            None => Ok(objects),
+            Some(c) => Err(Error::parse(format!("unexpected '{}' after end of records", c))),
+        }
+    }
+
+}
+
+fn get_number(object: &BTreeMap<String, JsonValue>, key: &str) -> Result<u64, Error> {
+    match object.get(key) {
+        Some(JsonValue::Number(n)) => Ok(*n),
+        _ => Err(Error::parse(format!("missing number: {}", key))),
+    }
+}
+
+fn get_string(object: &BTreeMap<String, JsonValue>, key: &str) -> Result<String, Error> {
+    match object.get(key) {
+        Some(JsonValue::String(s)) => Ok(s.clone()),
+        _ => Err(Error::parse(format!("missing string: {}", key))),
+    }
+}
+
+pub fn from_json(s: &str) -> Result<Vec<Record>, Error> {
+    JsonReader::new(s).read_objects()?.iter().map(|object| {
+        Ok(Record {
+            day: get_number(object, "day")? as usize,
+            phase: get_string(object, "phase")?,
+            stats: Stats {
+                samples: get_number(object, "samples")? as usize,
+                min: from_nanos(get_number(object, "min_ns")?),
+                median: from_nanos(get_number(object, "median_ns")?),
+                mean: from_nanos(get_number(object, "mean_ns")?),
+                stddev: from_nanos(get_number(object, "stddev_ns")?),
+            },
+        })
+    }).collect()
+}
+
+pub fn write(path: &Path, records: &[Record]) -> Result<(), Error> {
+    let content = match Format::from_path(path)? {
+        Format::Json => to_json(records),
+        Format::Csv => to_csv(records),
+    };
+
+    fs::write(path, content).map_err(|e| Error::io(format!("cannot write {}", path.display()), e))
+}
+
+pub fn read(path: &Path) -> Result<Vec<Record>, Error> {
+    let format = Format::from_path(path)?;
+    let content = fs::read_to_string(path).map_err(|e| Error::io(format!("cannot read {}", path.display()), e))?;
+
+    match format {
+        Format::Json => from_json(&content),
+        Format::Csv => from_csv(&content),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: usize,
+    pub previous: Duration,
+    pub current: Duration,
+    pub change_percent: f64,
+    pub slower: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Threshold(pub f64);
+
+impl FromStr for Threshold {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim_end_matches('%').parse::<f64>() {
+            Ok(percent) if percent >= 0.0 => Ok(Threshold(percent)),
+            _ => Err(Error::parse(format!("invalid threshold: {}", s))),
+        }
+    }
+}
+
+impl Default for Threshold {
+    fn default() -> Self {
+        Threshold(10.0)
+    }
+}
+
+pub fn compare(previous: &[Record], current: &[Record], threshold: Threshold) -> Vec<Comparison> {
+    let Threshold(threshold) = threshold;
+
+    current.iter()
+        .filter(|r| r.phase == "total")
+        .filter_map(|cur| {
+            let prev = previous.iter().find(|p| p.day == cur.day && p.phase == cur.phase)?;
+
+            let previous = prev.stats.median;
+            let current = cur.stats.median;
+            let change_percent = if previous.is_zero() {
+                0.0
+            } else {
+                (current.as_secs_f64() / previous.as_secs_f64() - 1.0) * 100.0
+            };
+
+            Some(Comparison {
+                day: cur.day,
+                previous,
+                current,
+                change_percent,
+                slower: change_percent > threshold,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        let stats = |median: u64| Stats {
+            samples: 5,
+            min: Duration::from_nanos(median - 10),
+            median: Duration::from_nanos(median),
+            mean: Duration::from_nanos(median + 5),
+            stddev: Duration::from_nanos(3),
+        };
+
+        vec![
+            Record { day: 1, phase: "parse".to_string(), stats: stats(100) },
+            Record { day: 1, phase: "total".to_string(), stats: stats(1000) },
+            Record { day: 12, phase: "total".to_string(), stats: stats(2000) },
+        ]
+    }
+
+    #[test]
+    fn test_csv_roundtrip() {
+        let csv = to_csv(&records());
+        assert!(csv.starts_with(CSV_HEADER));
+        assert_eq!(records(), from_csv(&csv).unwrap());
+
+        assert!(from_csv("1,total,5,1,2,3,4").is_err());
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let json = to_json(&records());
+        assert_eq!(records(), from_json(&json).unwrap());
+        assert_eq!(Vec::<Record>::new(), from_json(&to_json(&[])).unwrap());
+
+        assert!(from_json("[{\"day\": 1}]").is_err());
+        assert!(from_json("[").is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let previous = records();
+        let mut current = records();
+        current[1].stats.median = Duration::from_nanos(1050);
+        current[2].stats.median = Duration::from_nanos(3000);
+
+        let comparisons = compare(&previous, &current, Threshold::default());
+
+        assert_eq!(2, comparisons.len());
+        assert!(!comparisons[0].slower);
+        assert!(comparisons[1].slower);
+        assert_eq!(50.0, comparisons[1].change_percent.round());
+
+        assert_eq!(Threshold(5.0), "5%".parse().unwrap());
+        assert!("-1".parse::<Threshold>().is_err());
+    }
+
+}
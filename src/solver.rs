@@ -0,0 +1,196 @@
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use crate::utils::Error;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl FromStr for Part {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(Error::parse(format!("invalid part: {}", s))),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+pub trait Solver {
+    type Puzzle;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Puzzle, Error>;
+    fn part1(puzzle: &Self::Puzzle) -> Result<Self::Answer1, Error>;
+    fn part2(puzzle: &Self::Puzzle) -> Result<Self::Answer2, Error>;
+}
+
+pub trait Puzzle {
+    fn part1(&self) -> Result<String, Error>;
+    fn part2(&self) -> Result<String, Error>;
+
+    fn solve(&self, part: Part) -> Result<String, Error> {
+        match part {
+            Part::One => self.part1(),
+            Part::Two => self.part2(),
+        }
+    }
+}
+
+struct ParsedPuzzle<S: Solver>(S::Puzzle, PhantomData<S>);
+
+impl<S: Solver> Puzzle for ParsedPuzzle<S> {
+    fn part1(&self) -> Result<String, Error> {
+        S::part1(&self.0).map(|a| a.to_string())
+    }
+
+    fn part2(&self) -> Result<String, Error> {
+        S::part2(&self.0).map(|a| a.to_string())
+    }
+}
+
+fn parse_boxed<S: Solver + 'static>(input: &str) -> Result<Box<dyn Puzzle>, Error> {
+    let puzzle = S::parse(input)?;
+    Ok(Box::new(ParsedPuzzle::<S>(puzzle, PhantomData)))
+}
+
+pub struct DayEntry {
+    day: usize,
+    input_name: &'static str,
+    input: &'static str,
+    parse: fn(&str) -> Result<Box<dyn Puzzle>, Error>,
+}
+
+impl DayEntry {
+
+    pub fn new<S: Solver + 'static>(day: usize, input_name: &'static str, input: &'static str) -> DayEntry {
+        DayEntry {
+            day,
+            input_name,
+            input,
+            parse: parse_boxed::<S>,
+        }
+    }
+
+    pub fn day(&self) -> usize {
+        self.day
+    }
+
+    pub fn input_name(&self) -> &'static str {
+        self.input_name
+    }
+
+    pub fn is_example_of(&self, file_name: &str) -> bool {
+        let stem = self.input_name.trim_end_matches(".txt");
+        file_name.starts_with(&format!("{}_example", stem)) && file_name.ends_with(".txt")
+    }
+
+    pub fn input(&self) -> &'static str {
+        self.input
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Puzzle>, Error> {
+        (self.parse)(input)
+    }
+
+    pub fn solve(&self, input: &str, part: Part) -> Result<String, Error> {
+        self.parse(input)?.solve(part)
+    }
+}
+
+macro_rules! day_entry {
+    ($day:expr, $solver:ty, $input_name:literal) => {
+        DayEntry::new::<$solver>($day, $input_name, include_str!(concat!("../res/", $input_name)))
+    };
+}
+
+pub fn registry() -> Vec<DayEntry> {
+    use crate::*;
+
+    vec![
+        day_entry!(1, day1::Day1, "day1-calories.txt"),
+        day_entry!(2, day2::Day2, "day2-guide.txt"),
+        day_entry!(3, day3::Day3, "day3-rucksack.txt"),
+        day_entry!(4, day4::Day4, "day4-ranges.txt"),
+        day_entry!(5, day5::Day5, "day5-stacks.txt"),
+        day_entry!(6, day6::Day6, "day6-code.txt"),
+        day_entry!(7, day7::Day7, "day7-bash.txt"),
+        day_entry!(8, day8::Day8, "day8-trees.txt"),
+        day_entry!(9, day9::Day9, "day9-steps.txt"),
+        day_entry!(10, day10::Day10, "day10-instr.txt"),
+        day_entry!(11, day11::Day11, "day11-apes.txt"),
+        day_entry!(12, day12::Day12, "day12-map.txt"),
+        day_entry!(13, day13::Day13, "day13-lists.txt"),
+        day_entry!(14, day14::Day14, "day14-paths.txt"),
+        day_entry!(15, day15::Day15, "day15-beacons.txt"),
+        day_entry!(18, day18::Day18, "day18-faces.txt"),
+        day_entry!(20, day20::Day20, "day20-enc.txt"),
+        day_entry!(21, day21::Day21, "day21-shout.txt"),
+        day_entry!(22, day22::Day22, "day22-map.txt"),
+    ]
+}
+
+pub fn find_day(day: usize) -> Option<DayEntry> {
+    registry().into_iter().find(|e| e.day == day)
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let days: Vec<usize> = registry().iter().map(DayEntry::day).collect();
+
+        let mut sorted_days = days.clone();
+        sorted_days.sort();
+        sorted_days.dedup();
+
+        assert_eq!(sorted_days, days);
+        assert!(find_day(16).is_none());
+    }
+
+    #[test]
+    fn test_is_example_of() {
+        let day9 = find_day(9).unwrap();
+
+        assert!(day9.is_example_of("day9-steps_example.txt"));
+        assert!(day9.is_example_of("day9-steps_example2.txt"));
+        assert!(!day9.is_example_of("day9-steps.txt"));
+
+        let day22 = find_day(22).unwrap();
+        assert!(!day22.is_example_of("day22-regions_example.txt"));
+    }
+
+    #[test]
+    fn test_solve_examples() {
+        let day1 = find_day(1).unwrap();
+        let example = include_str!("../res/day1-calories_example.txt");
+
+        assert_eq!("24000", day1.solve(example, Part::One).unwrap());
+        assert_eq!("45000", day1.solve(example, Part::Two).unwrap());
+
+        let day12 = find_day(12).unwrap();
+        let puzzle = day12.parse(include_str!("../res/day12-map_example.txt")).unwrap();
+
+        assert_eq!("31", puzzle.part1().unwrap());
+        assert_eq!("29", puzzle.part2().unwrap());
+    }
+
+}
@@ -8,14 +8,54 @@ pub trait Graph {
 
     fn get_property(&self, pos: &Self::Position) -> &Self::Property;
     fn get_surroundings(&self, pos: &Self::Position) -> Vec<Self::Position>;
+
+    // the positions that have pos in their surroundings, directed graphs have to override this
+    fn get_predecessors(&self, pos: &Self::Position) -> Vec<Self::Position> {
+        self.get_surroundings(pos)
+    }
 }
 
-pub struct Bfs<'a, F, G>
+// decides whether the search may move from one position to a neighbouring one
+pub trait EdgeFilter<G: Graph> {
+    fn allows(&self, graph: &G, from: &G::Position, to: &G::Position) -> bool;
+}
+
+// a filter that only looks at the properties of both positions
+pub struct PropertyFilter<F>(F);
+
+// a filter that sees both positions together with their properties
+pub struct PositionFilter<F>(F);
+
+impl<G, F> EdgeFilter<G> for PropertyFilter<F>
     where F: Fn(&G::Property, &G::Property) -> bool,
           G: Graph,
+{
+    fn allows(&self, graph: &G, from: &G::Position, to: &G::Position) -> bool {
+        let PropertyFilter(filter) = self;
+        filter(graph.get_property(from), graph.get_property(to))
+    }
+}
+
+impl<G, F> EdgeFilter<G> for PositionFilter<F>
+    where F: Fn(&G::Position, &G::Property, &G::Position, &G::Property) -> bool,
+          G: Graph,
+{
+    fn allows(&self, graph: &G, from: &G::Position, to: &G::Position) -> bool {
+        let PositionFilter(filter) = self;
+        filter(from, graph.get_property(from), to, graph.get_property(to))
+    }
+}
+
+// position, predecessor, source and distance
+type FrontierEntry<P> = (P, Option<P>, P, u64);
+
+pub struct Bfs<'a, F, G>
+    where F: EdgeFilter<G>,
+          G: Graph,
 {
     graph: &'a G,
     filter: F,
+    reversed: bool,
 }
 
 pub fn bfs<G>(graph: &G, start_pos: &G::Position) -> HashMap<G::Position, u64>
@@ -31,51 +71,315 @@ pub fn filtered_bfs<G, F>(graph: &G, filter: F, start_pos: &G::Position) -> Hash
     Bfs::new(graph, filter).run(start_pos)
 }
 
-impl<'a, F, G> Bfs<'a, F, G>
+impl<'a, F, G> Bfs<'a, PropertyFilter<F>, G>
     where F: Fn(&G::Property, &G::Property) -> bool,
         G: Graph,
 {
 
-    pub fn new(graph: &'a G, filter: F) -> Bfs<'a, F, G> {
+    pub fn new(graph: &'a G, filter: F) -> Bfs<'a, PropertyFilter<F>, G> {
+        Bfs::with_filter(graph, PropertyFilter(filter))
+    }
+
+}
+
+impl<'a, F, G> Bfs<'a, PositionFilter<F>, G>
+    where F: Fn(&G::Position, &G::Property, &G::Position, &G::Property) -> bool,
+        G: Graph,
+{
+
+    // the filter is called with the current position and property, then the next position and property
+    pub fn with_positions(graph: &'a G, filter: F) -> Bfs<'a, PositionFilter<F>, G> {
+        Bfs::with_filter(graph, PositionFilter(filter))
+    }
+
+}
+
+impl<'a, F, G> Bfs<'a, F, G>
+    where F: EdgeFilter<G>,
+        G: Graph,
+{
+
+    pub fn with_filter(graph: &'a G, filter: F) -> Bfs<'a, F, G> {
         Bfs {
             graph,
             filter,
+            reversed: false,
+        }
+    }
+
+    // follows the edges backwards, from a position to its predecessors
+    pub fn reversed(self) -> Bfs<'a, F, G> {
+        Bfs {
+            reversed: !self.reversed,
+            ..self
+        }
+    }
+
+    fn can_move(&self, cur: &G::Position, next: &G::Position) -> bool {
+        if self.reversed {
+            self.filter.allows(self.graph, next, cur)
+        } else {
+            self.filter.allows(self.graph, cur, next)
         }
     }
 
-    pub fn run(&self, start_pos: &'a G::Position) -> HashMap<G::Position, u64> {
-        let mut frontier: VecDeque<(G::Position, u64)> = VecDeque::new();
-        frontier.push_back((start_pos.clone(), 0));
+    pub fn run(&self, start_pos: &G::Position) -> HashMap<G::Position, u64> {
+        self.search([start_pos], |_| false).dists
+    }
+
+    pub fn run_to(&self, start_pos: &G::Position, end_pos: &G::Position) -> BfsResult<G::Position> {
+        self.search([start_pos], |pos| pos == end_pos)
+    }
+
+    pub fn run_until(&self, start_pos: &G::Position, stop: impl Fn(&G::Position) -> bool) -> BfsResult<G::Position> {
+        self.search([start_pos], stop)
+    }
+
+    // all start positions have distance zero, every position remembers the closest start it was reached from
+    pub fn run_from<'b>(&self, start_positions: impl IntoIterator<Item=&'b G::Position>) -> BfsResult<G::Position> where G::Position: 'b {
+        self.search(start_positions, |_| false)
+    }
+
+    pub fn run_from_until<'b>(&self, start_positions: impl IntoIterator<Item=&'b G::Position>, stop: impl Fn(&G::Position) -> bool) -> BfsResult<G::Position> where G::Position: 'b {
+        self.search(start_positions, stop)
+    }
+
+    // explores the graph until a position matching stop is reached, or everything reachable is visited
+    fn search<'b>(&self, start_positions: impl IntoIterator<Item=&'b G::Position>, stop: impl Fn(&G::Position) -> bool) -> BfsResult<G::Position> where G::Position: 'b {
+        let mut frontier: VecDeque<FrontierEntry<G::Position>> = start_positions.into_iter()
+            .map(|start_pos| (start_pos.clone(), None, start_pos.clone(), 0))
+            .collect();
 
-        let mut dists: HashMap<G::Position, u64> = HashMap::new();
+        let mut result = BfsResult {
+            dists: HashMap::new(),
+            predecessors: HashMap::new(),
+            sources: HashMap::new(),
+            reached: None,
+        };
 
-        while let Some((cur, dist)) = frontier.pop_front() {
+        while let Some((cur, predecessor, source, dist)) = frontier.pop_front() {
 
-            if dists.contains_key(&cur) {
+            if result.dists.contains_key(&cur) {
                 continue;
             }
 
-            dists.insert(cur.clone(), dist);
+            result.dists.insert(cur.clone(), dist);
+            result.sources.insert(cur.clone(), source.clone());
+            if let Some(predecessor) = predecessor {
+                result.predecessors.insert(cur.clone(), predecessor);
+            }
+
+            if stop(&cur) {
+                result.reached = Some(cur);
+                break;
+            }
 
-            let surroundings = self.graph.get_surroundings(&cur);
+            let surroundings = if self.reversed {
+                self.graph.get_predecessors(&cur)
+            } else {
+                self.graph.get_surroundings(&cur)
+            };
 
             for next in surroundings {
-                if dists.contains_key(&next) {
+                if result.dists.contains_key(&next) {
                     continue;
                 }
 
-                let cur_height = self.graph.get_property(&cur);
-                let next_height = self.graph.get_property(&next);
-
-                if !(self.filter)(cur_height, next_height) {
+                if !self.can_move(&cur, &next) {
                     continue;
                 }
 
-                frontier.push_back((next, dist+1));
+                frontier.push_back((next, Some(cur.clone()), source.clone(), dist+1));
             }
 
         }
 
-        dists
+        result
+    }
+}
+
+#[derive(Debug)]
+pub struct BfsResult<P> {
+    dists: HashMap<P, u64>,
+    predecessors: HashMap<P, P>,
+    sources: HashMap<P, P>,
+    reached: Option<P>,
+}
+
+impl<P: Clone + Eq + Hash> BfsResult<P> {
+
+    pub fn dists(&self) -> &HashMap<P, u64> {
+        &self.dists
+    }
+
+    pub fn dist(&self, pos: &P) -> Option<u64> {
+        self.dists.get(pos).copied()
     }
+
+    // the start position the shortest path to pos begins at
+    pub fn source(&self, pos: &P) -> Option<&P> {
+        self.sources.get(pos)
+    }
+
+    // the position that stopped the search early, if any
+    pub fn reached(&self) -> Option<&P> {
+        self.reached.as_ref()
+    }
+
+    // the shortest path from its source to end_pos, both included
+    pub fn path_to(&self, end_pos: &P) -> Option<Vec<P>> {
+        if !self.dists.contains_key(end_pos) {
+            return None;
+        }
+
+        let mut path = vec![end_pos.clone()];
+        while let Some(predecessor) = self.predecessors.get(path.last()?) {
+            path.push(predecessor.clone());
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+}
+
+// pairs every position with the number of steps taken to reach it, so filters can depend on time
+pub struct TimeExpanded<'a, G: Graph> {
+    graph: &'a G,
+    period: Option<usize>,
+    wait: bool,
+}
+
+impl<'a, G: Graph> TimeExpanded<'a, G> {
+
+    pub fn new(graph: &'a G) -> TimeExpanded<'a, G> {
+        TimeExpanded {
+            graph,
+            period: None,
+            wait: true,
+        }
+    }
+
+    // the step counter wraps around, which keeps the expanded graph finite for periodic obstacles
+    pub fn with_period(self, period: usize) -> TimeExpanded<'a, G> {
+        TimeExpanded {
+            period: Some(period),
+            ..self
+        }
+    }
+
+    pub fn without_waiting(self) -> TimeExpanded<'a, G> {
+        TimeExpanded {
+            wait: false,
+            ..self
+        }
+    }
+
+    fn next_step(&self, step: usize) -> usize {
+        match self.period {
+            Some(period) => (step + 1) % period,
+            None => step + 1,
+        }
+    }
+
+    // None before the first step of an unbounded graph
+    fn previous_step(&self, step: usize) -> Option<usize> {
+        match self.period {
+            Some(period) => Some((step + period - 1) % period),
+            None => step.checked_sub(1),
+        }
+    }
+
+}
+
+impl<'a, G: Graph> Graph for TimeExpanded<'a, G> {
+    type Position = (G::Position, usize);
+    type Property = G::Property;
+
+    fn get_property(&self, (pos, _): &Self::Position) -> &Self::Property {
+        self.graph.get_property(pos)
+    }
+
+    fn get_surroundings(&self, (pos, step): &Self::Position) -> Vec<Self::Position> {
+        let next_step = self.next_step(*step);
+        let mut surroundings: Vec<Self::Position> = self.graph.get_surroundings(pos).into_iter()
+            .map(|next| (next, next_step))
+            .collect();
+
+        if self.wait {
+            surroundings.push((pos.clone(), next_step));
+        }
+
+        surroundings
+    }
+
+    fn get_predecessors(&self, (pos, step): &Self::Position) -> Vec<Self::Position> {
+        let Some(previous_step) = self.previous_step(*step) else {
+            return Vec::new();
+        };
+        let mut predecessors: Vec<Self::Position> = self.graph.get_predecessors(pos).into_iter()
+            .map(|previous| (previous, previous_step))
+            .collect();
+
+        if self.wait {
+            predecessors.push((pos.clone(), previous_step));
+        }
+
+        predecessors
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::utils::grid::{Grid, GridPos};
+    use crate::utils::vec2::Vec2;
+
+    fn open_grid() -> Grid<char> {
+        Grid::parse_with("...\n.#.\n...\n", |_, c| Ok(c)).unwrap()
+    }
+
+    #[test]
+    fn test_position_filter() {
+        let grid = open_grid();
+
+        // the bottom right corner can only be entered from above
+        let bfs = Bfs::with_positions(&grid, |from: &GridPos, _, to: &GridPos, next: &char| {
+            *next != '#' && (*to != Vec2(2, 2) || from.1 < to.1)
+        });
+        let result = bfs.run_to(&Vec2(0, 2), &Vec2(2, 2));
+        assert_eq!(Some(6), result.dist(&Vec2(2, 2)));
+        assert_eq!(Some(2), Bfs::new(&grid, |_, next| *next != '#').run_to(&Vec2(0, 2), &Vec2(2, 2)).dist(&Vec2(2, 2)));
+    }
+
+    #[test]
+    fn test_time_expanded() {
+        let grid = open_grid();
+        let expanded = TimeExpanded::new(&grid).with_period(4);
+
+        // the middle of the top row is blocked during the first two steps
+        let bfs = Bfs::with_positions(&expanded, |_, _, (to, step): &(GridPos, usize), next: &char| {
+            *next != '#' && !(*to == Vec2(1, 0) && *step < 3)
+        });
+        let result = bfs.run_until(&(Vec2(0, 0), 0), |(pos, _)| *pos == Vec2(2, 0));
+        let (end_pos, step) = result.reached().unwrap();
+        // 4 steps, wrapped around by the period
+        assert_eq!((&Vec2(2, 0), 0), (end_pos, *step));
+        assert_eq!(Some(4), result.dist(&(Vec2(2, 0), 0)));
+
+        let no_wait = TimeExpanded::new(&grid).without_waiting();
+        assert_eq!(Some(&2), bfs_time(&no_wait).get(&(Vec2(2, 0), 2)));
+
+        // backwards in time, the start is two steps before (2, 0) at step 2
+        let result = Bfs::new(&no_wait, |_, next| *next != '#').reversed()
+            .run_until(&(Vec2(2, 0), 2), |pos| *pos == (Vec2(0, 0), 0));
+        assert_eq!(Some(2), result.dist(&(Vec2(0, 0), 0)));
+        assert!(result.dists().keys().all(|(_, step)| *step <= 2));
+    }
+
+    fn bfs_time(graph: &TimeExpanded<Grid<char>>) -> HashMap<(GridPos, usize), u64> {
+        Bfs::new(graph, |_, next| *next != '#').run_until(&(Vec2(0, 0), 0), |(_, step)| *step > 4).dists().clone()
+    }
+
 }
@@ -0,0 +1,320 @@
+use std::ops::{Neg, RangeInclusive};
+use crate::utils::num::{Integer, One};
+use crate::utils::vec2::Vec2;
+use crate::utils::vec3::Vec3;
+
+// a point with integer components, one per axis
+pub trait Point: Clone {
+    type Scalar: Integer;
+
+    const DIM: usize;
+
+    // axis must be below DIM
+    fn component(&self, axis: usize) -> Self::Scalar;
+
+    // calls f once per axis, in axis order
+    fn from_fn<F: FnMut(usize) -> Self::Scalar>(f: F) -> Self;
+}
+
+impl<T: Integer> Point for Vec2<T> {
+    type Scalar = T;
+
+    const DIM: usize = 2;
+
+    fn component(&self, axis: usize) -> T {
+        let Vec2(x, y) = self;
+        [*x, *y][axis]
+    }
+
+    fn from_fn<F: FnMut(usize) -> T>(mut f: F) -> Self {
+        Vec2(f(0), f(1))
+    }
+}
+
+impl<T: Integer> Point for Vec3<T> {
+    type Scalar = T;
+
+    const DIM: usize = 3;
+
+    fn component(&self, axis: usize) -> T {
+        let Vec3(x, y, z) = self;
+        [*x, *y, *z][axis]
+    }
+
+    fn from_fn<F: FnMut(usize) -> T>(mut f: F) -> Self {
+        Vec3(f(0), f(1), f(2))
+    }
+}
+
+fn zip_with<P, F>(a: &P, b: &P, f: F) -> P
+    where P: Point,
+          F: Fn(P::Scalar, P::Scalar) -> P::Scalar,
+{
+    P::from_fn(|i| f(a.component(i), b.component(i)))
+}
+
+fn map_with<P, F>(a: &P, f: F) -> P
+    where P: Point,
+          F: Fn(P::Scalar) -> P::Scalar,
+{
+    P::from_fn(|i| f(a.component(i)))
+}
+
+// the smallest axis aligned box containing a set of points, min and max are inclusive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundingBox<P> {
+    min: P,
+    max: P,
+}
+
+impl<P: Point> BoundingBox<P> {
+
+    // the box spanned by two opposite corners, in any order
+    pub fn new(a: P, b: P) -> BoundingBox<P> {
+        BoundingBox {
+            min: zip_with(&a, &b, std::cmp::min),
+            max: zip_with(&a, &b, std::cmp::max),
+        }
+    }
+
+    pub fn from_point(point: P) -> BoundingBox<P> {
+        BoundingBox {
+            min: point.clone(),
+            max: point,
+        }
+    }
+
+    // None for an empty iterator
+    pub fn enclosing(points: impl IntoIterator<Item=P>) -> Option<BoundingBox<P>> {
+        let mut points = points.into_iter();
+        let mut bounds = BoundingBox::from_point(points.next()?);
+        for point in points {
+            bounds.include(&point);
+        }
+        Some(bounds)
+    }
+
+    pub fn min(&self) -> &P {
+        &self.min
+    }
+
+    pub fn max(&self) -> &P {
+        &self.max
+    }
+
+    // grows the box just enough to contain point
+    pub fn include(&mut self, point: &P) {
+        self.min = zip_with(&self.min, point, std::cmp::min);
+        self.max = zip_with(&self.max, point, std::cmp::max);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        BoundingBox {
+            min: zip_with(&self.min, &other.min, std::cmp::min),
+            max: zip_with(&self.max, &other.max, std::cmp::max),
+        }
+    }
+
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let min = zip_with(&self.min, &other.min, std::cmp::max);
+        let max = zip_with(&self.max, &other.max, std::cmp::min);
+
+        let is_empty = (0..P::DIM).any(|i| min.component(i) > max.component(i));
+        (!is_empty).then_some(BoundingBox { min, max })
+    }
+
+    pub fn contains(&self, point: &P) -> bool {
+        (0..P::DIM).all(|i| self.axis(i).contains(&point.component(i)))
+    }
+
+    pub fn axis(&self, axis: usize) -> RangeInclusive<P::Scalar> {
+        self.min.component(axis)..=self.max.component(axis)
+    }
+
+    // a copy of the box with the extent on one axis replaced, None for an empty range
+    pub fn with_axis(&self, axis: usize, range: RangeInclusive<P::Scalar>) -> Option<Self> {
+        let (start, end) = range.into_inner();
+        (start <= end).then(|| BoundingBox {
+            min: P::from_fn(|i| if i == axis { start } else { self.min.component(i) }),
+            max: P::from_fn(|i| if i == axis { end } else { self.max.component(i) }),
+        })
+    }
+
+    // the number of points on every axis
+    pub fn size(&self) -> P {
+        zip_with(&self.min, &self.max, |lo, hi| hi - lo + P::Scalar::one())
+    }
+
+    // the number of contained points, the volume for 3D boxes
+    pub fn area(&self) -> P::Scalar {
+        let size = self.size();
+        (0..P::DIM).fold(P::Scalar::one(), |acc, i| acc * size.component(i))
+    }
+
+    // all contained points, the first axis changes fastest
+    pub fn points(&self) -> Points<P> {
+        Points {
+            bounds: self.clone(),
+            next: Some(self.min.clone()),
+        }
+    }
+
+}
+
+// padding needs room below the minimum, so it is limited to signed scalars
+impl<P: Point> BoundingBox<P> where P::Scalar: Neg<Output = P::Scalar> {
+
+    // grows the box by amount in every direction, a negative amount shrinks it
+    pub fn padded(&self, amount: P::Scalar) -> Self {
+        BoundingBox {
+            min: map_with(&self.min, |v| v - amount),
+            max: map_with(&self.max, |v| v + amount),
+        }
+    }
+
+}
+
+impl<T: Integer> BoundingBox<Vec2<T>> {
+
+    pub fn top_left(&self) -> &Vec2<T> {
+        &self.min
+    }
+
+    pub fn bottom_right(&self) -> &Vec2<T> {
+        &self.max
+    }
+
+    pub fn top_right(&self) -> Vec2<T> {
+        let (Vec2(_, min_y), Vec2(max_x, _)) = (&self.min, &self.max);
+        Vec2(*max_x, *min_y)
+    }
+
+    pub fn bottom_left(&self) -> Vec2<T> {
+        let (Vec2(min_x, _), Vec2(_, max_y)) = (&self.min, &self.max);
+        Vec2(*min_x, *max_y)
+    }
+
+    pub fn x_range(&self) -> RangeInclusive<T> {
+        let (Vec2(min_x, _), Vec2(max_x, _)) = (&self.min, &self.max);
+        *min_x..=*max_x
+    }
+
+    pub fn y_range(&self) -> RangeInclusive<T> {
+        let (Vec2(_, min_y), Vec2(_, max_y)) = (&self.min, &self.max);
+        *min_y..=*max_y
+    }
+
+}
+
+impl<T: Integer> BoundingBox<Vec3<T>> {
+
+    pub fn x_range(&self) -> RangeInclusive<T> {
+        let (Vec3(min_x, _, _), Vec3(max_x, _, _)) = (&self.min, &self.max);
+        *min_x..=*max_x
+    }
+
+    pub fn y_range(&self) -> RangeInclusive<T> {
+        let (Vec3(_, min_y, _), Vec3(_, max_y, _)) = (&self.min, &self.max);
+        *min_y..=*max_y
+    }
+
+    pub fn z_range(&self) -> RangeInclusive<T> {
+        let (Vec3(_, _, min_z), Vec3(_, _, max_z)) = (&self.min, &self.max);
+        *min_z..=*max_z
+    }
+
+}
+
+// panics for empty iterators, use BoundingBox::enclosing if the points might be empty
+impl<P: Point> FromIterator<P> for BoundingBox<P> {
+    fn from_iter<I: IntoIterator<Item=P>>(iter: I) -> Self {
+        BoundingBox::enclosing(iter).expect("can only compute bounding box for non empty iterators")
+    }
+}
+
+impl<'a, P: 'a + Point> FromIterator<&'a P> for BoundingBox<P> {
+    fn from_iter<I: IntoIterator<Item=&'a P>>(iter: I) -> Self {
+        iter.into_iter().cloned().collect()
+    }
+}
+
+pub struct Points<P> {
+    bounds: BoundingBox<P>,
+    next: Option<P>,
+}
+
+impl<P: Point> Iterator for Points<P> {
+    type Item = P;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cur = self.next.take()?;
+
+        // counts up like an odometer, wrapping an axis carries over to the next one
+        let mut carry = true;
+        let next = P::from_fn(|i| {
+            let v = cur.component(i);
+            if !carry {
+                v
+            } else if v >= self.bounds.max.component(i) {
+                self.bounds.min.component(i)
+            } else {
+                carry = false;
+                v + P::Scalar::one()
+            }
+        });
+
+        if !carry {
+            self.next = Some(next);
+        }
+
+        Some(cur)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_bounds_2d() {
+        let points = [Vec2(3, -1), Vec2(0, 4), Vec2(2, 2)];
+
+        let bounds: BoundingBox<Vec2<i32>> = points.iter().collect();
+        assert_eq!(BoundingBox::new(Vec2(3, 4), Vec2(0, -1)), bounds);
+        assert_eq!(&Vec2(0, -1), bounds.top_left());
+        assert_eq!(Vec2(3, -1), bounds.top_right());
+        assert_eq!(Vec2(0, 4), bounds.bottom_left());
+        assert_eq!(24, bounds.area());
+        assert!(points.iter().all(|p| bounds.contains(p)));
+        assert!(!bounds.contains(&Vec2(4, 0)));
+        assert_eq!(None, BoundingBox::<Vec2<i32>>::enclosing([]));
+
+        let mut grown = BoundingBox::from_point(Vec2(5, 5));
+        grown.include(&Vec2(6, 3));
+        assert_eq!(BoundingBox::new(Vec2(5, 3), Vec2(6, 5)), grown);
+        assert_eq!(BoundingBox::new(Vec2(0, -1), Vec2(6, 5)), bounds.union(&grown));
+        assert_eq!(None, bounds.intersect(&grown));
+        assert_eq!(Some(BoundingBox::new(Vec2(3, 1), Vec2(3, 4))), bounds.intersect(&grown.padded(2)));
+        assert_eq!(BoundingBox::new(Vec2(4, 2), Vec2(7, 6)), grown.padded(1));
+    }
+
+    #[test]
+    fn test_points() {
+        let rect = BoundingBox::new(Vec2(1usize, 5), Vec2(2, 6));
+        assert_eq!(vec![Vec2(1, 5), Vec2(2, 5), Vec2(1, 6), Vec2(2, 6)], rect.points().collect::<Vec<_>>());
+
+        let cuboid = BoundingBox::new(Vec3(0, 0, 0), Vec3(1, 2, 3));
+        assert_eq!(24, cuboid.area());
+        assert_eq!(24, cuboid.points().count());
+        assert_eq!(Some(Vec3(1, 2, 3)), cuboid.points().last());
+        assert_eq!(0..=3, cuboid.z_range());
+        assert_eq!(cuboid.z_range(), cuboid.axis(2));
+        assert_eq!(Some(BoundingBox::new(Vec3(0, 5, 0), Vec3(1, 6, 3))), cuboid.with_axis(1, 5..=6));
+        assert_eq!(None, cuboid.with_axis(1, 6..=5));
+        assert!(cuboid.points().all(|p| cuboid.contains(&p)));
+
+        assert_eq!(1, BoundingBox::from_point(Vec3(7, 7, 7)).points().count());
+    }
+
+}
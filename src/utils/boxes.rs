@@ -0,0 +1,155 @@
+use std::iter::Sum;
+use crate::utils::bounding_box::{BoundingBox, Point};
+use crate::utils::num::{Decrement, Increment};
+use crate::utils::ranges::RangeExt;
+use crate::utils::vec2::Vec2;
+use crate::utils::vec3::Vec3;
+
+// axis aligned boxes are bounding boxes, every axis is an inclusive range
+pub type Rect<T> = BoundingBox<Vec2<T>>;
+pub type Cuboid<T> = BoundingBox<Vec3<T>>;
+
+impl<P: Point> BoundingBox<P>
+where P::Scalar: Decrement<Output = P::Scalar> + Increment<Output = P::Scalar>
+{
+    pub fn contains_fully(&self, other: &Self) -> bool {
+        (0..P::DIM).all(|i| self.axis(i).contains_fully(&other.axis(i)))
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersect(other).is_some()
+    }
+
+    // disjoint pieces covering self without other, at most two per axis
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        if !self.intersects(other) {
+            return vec![self.clone()];
+        }
+
+        let mut pieces = Vec::new();
+        let mut rest = self.clone();
+
+        for i in 0..P::DIM {
+            let (before, inside, after) = rest.axis(i).split_off(&other.axis(i));
+
+            pieces.extend([before, after].into_iter().flatten().filter_map(|piece| rest.with_axis(i, piece)));
+
+            if let Some(inside) = inside.and_then(|inside| rest.with_axis(i, inside)) {
+                rest = inside;
+            }
+        }
+
+        pieces
+    }
+}
+
+// a union of boxes, stored as disjoint pieces
+#[derive(Debug, Clone)]
+pub struct BoxSet<P> {
+    boxes: Vec<BoundingBox<P>>,
+}
+
+impl<P> Default for BoxSet<P> {
+    fn default() -> Self {
+        BoxSet {
+            boxes: Vec::new(),
+        }
+    }
+}
+
+impl<P: Point> BoxSet<P>
+where P::Scalar: Decrement<Output = P::Scalar> + Increment<Output = P::Scalar>
+{
+    pub fn new() -> BoxSet<P> {
+        BoxSet::default()
+    }
+
+    pub fn boxes(&self) -> &[BoundingBox<P>] {
+        &self.boxes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.boxes.is_empty()
+    }
+
+    pub fn contains(&self, point: &P) -> bool {
+        self.boxes.iter().any(|b| b.contains(point))
+    }
+
+    pub fn insert(&mut self, new_box: BoundingBox<P>) {
+        self.remove(&new_box);
+        self.boxes.push(new_box);
+    }
+
+    pub fn remove(&mut self, removed: &BoundingBox<P>) {
+        self.boxes = self.boxes.iter()
+            .flat_map(|b| b.subtract(removed))
+            .collect();
+    }
+
+    pub fn volume(&self) -> P::Scalar where P::Scalar: Sum {
+        self.boxes.iter().map(BoundingBox::area).sum()
+    }
+}
+
+impl<P: Point> FromIterator<BoundingBox<P>> for BoxSet<P>
+where P::Scalar: Decrement<Output = P::Scalar> + Increment<Output = P::Scalar>
+{
+    fn from_iter<I: IntoIterator<Item=BoundingBox<P>>>(iter: I) -> Self {
+        let mut box_set = BoxSet::new();
+        for b in iter {
+            box_set.insert(b);
+        }
+        box_set
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn cube(min: i64, max: i64) -> Cuboid<i64> {
+        Cuboid::new(Vec3(min, min, min), Vec3(max, max, max))
+    }
+
+    #[test]
+    fn test_rect() {
+        let a = Rect::new(Vec2(0, 0), Vec2(3, 3));
+        let b = Rect::new(Vec2(2, 1), Vec2(5, 2));
+
+        assert_eq!(Some(Rect::new(Vec2(2, 1), Vec2(3, 2))), a.intersect(&b));
+        assert_eq!(16, a.area());
+        assert!(a.contains(&Vec2(3, 0)));
+        assert!(!a.contains(&Vec2(4, 0)));
+
+        let pieces = a.subtract(&b);
+        assert_eq!(12, pieces.iter().map(Rect::area).sum::<i64>());
+        assert!(pieces.iter().all(|p| !p.intersects(&b) && a.contains_fully(p)));
+        assert_eq!(vec![a.clone()], a.subtract(&Rect::new(Vec2(4, 4), Vec2(5, 5))));
+        assert!(b.subtract(&b).is_empty());
+    }
+
+    #[test]
+    fn test_reactor_steps() {
+        let mut reactor: BoxSet<Vec3<i64>> = BoxSet::new();
+
+        reactor.insert(cube(10, 12));
+        assert_eq!(27, reactor.volume());
+        reactor.insert(cube(11, 13));
+        assert_eq!(46, reactor.volume());
+        reactor.remove(&cube(9, 11));
+        assert_eq!(38, reactor.volume());
+        reactor.insert(cube(10, 10));
+        assert_eq!(39, reactor.volume());
+
+        assert!(reactor.contains(&Vec3(10, 10, 10)));
+        assert!(!reactor.contains(&Vec3(11, 11, 11)));
+
+        let pieces = reactor.boxes();
+        for (i, a) in pieces.iter().enumerate() {
+            assert!(pieces[i + 1..].iter().all(|b| !a.intersects(b)));
+        }
+    }
+
+}
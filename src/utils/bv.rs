@@ -1,68 +1,227 @@
-use std::mem::size_of;
-use std::ops::{BitAnd, BitOr};
-use std::marker::Copy;
+use std::cmp::max;
+use std::fmt::{Debug, Formatter};
+use std::hash::Hash;
+use std::ops::{BitAnd, BitOr, BitXor, Not};
 
-#[derive(Clone)]
+pub trait Word: Copy + Default + Eq + Hash
+    + BitAnd<Output=Self> + BitOr<Output=Self> + BitXor<Output=Self> + Not<Output=Self> {
+    const BITS: usize;
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn shl(self, bits: usize) -> Self;
+    fn count_ones(self) -> u32;
+    fn trailing_zeros(self) -> u32;
+}
+
+macro_rules! impl_word {
+    ($($t:ty),*) => {
+        $(
+            impl Word for $t {
+                const BITS: usize = <$t>::BITS as usize;
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn shl(self, bits: usize) -> Self {
+                    self << bits
+                }
+
+                fn count_ones(self) -> u32 {
+                    <$t>::count_ones(self)
+                }
+
+                fn trailing_zeros(self) -> u32 {
+                    <$t>::trailing_zeros(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_word!(u8, u16, u32, u64, u128);
+
+// a fixed size bitset of S words, indexing past the capacity panics
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ConstBitVec<const S: usize, T>([T; S]);
 
-impl<const S: usize, T: Default + Copy> Default for ConstBitVec<S, T> {
+impl<const S: usize, T: Word> Default for ConstBitVec<S, T> {
     fn default() -> Self {
-        ConstBitVec([T::default(); S])
+        ConstBitVec([T::ZERO; S])
     }
 }
 
-impl<const S: usize, T> ConstBitVec<S, T> {
+impl<const S: usize, T: Word> ConstBitVec<S, T> {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub const fn capacity() -> usize {
+        S * T::BITS
+    }
+
     fn index_to_word_idx(index: usize) -> usize {
-        let word_size: usize = size_of::<T>();
-        index / word_size
+        index / T::BITS
     }
 
-    fn index_to_pos_in_word(index: usize) -> usize {
-        let word_size: usize = size_of::<T>();
-        index % word_size
+    fn mask(index: usize) -> T {
+        T::ONE.shl(index % T::BITS)
     }
 
     fn get_word(&self, index: usize) -> &T {
         let ConstBitVec(bit_vec) = self;
-        let word_idx = Self::index_to_word_idx(index);
-        &bit_vec[word_idx]
+        &bit_vec[Self::index_to_word_idx(index)]
     }
 
     fn get_word_mut(&mut self, index: usize) -> &mut T {
         let ConstBitVec(bit_vec) = self;
-        let word_idx = Self::index_to_word_idx(index);
-        &mut bit_vec[word_idx]
+        &mut bit_vec[Self::index_to_word_idx(index)]
     }
-}
 
-impl<const S: usize, T> ConstBitVec<S, T>
-    where T: From<usize> + PartialEq + Copy + Default + BitAnd<Output=T>
-{
     pub fn is_set(&self, index: usize) -> bool {
-        let word = self.get_word(index);
-        let pos = Self::index_to_pos_in_word(index);
-        *word & (1 << pos).into() != 0.into()
+        *self.get_word(index) & Self::mask(index) != T::ZERO
     }
 
     pub fn clear_at(&mut self, index: usize) {
         let word = self.get_word_mut(index);
-        let pos = Self::index_to_pos_in_word(index);
-        *word = *word & (!(1 << pos)).into();
+        *word = *word & !Self::mask(index);
     }
-}
 
-impl<const S: usize, T> ConstBitVec<S, T>
-    where T: From<usize> + PartialEq + Copy + Default + BitOr<Output=T>
-{
     pub fn set_at(&mut self, index: usize) {
         let word = self.get_word_mut(index);
-        let pos = Self::index_to_pos_in_word(index);
-        *word = *word | (1 << pos).into();
+        *word = *word | Self::mask(index);
+    }
+
+    pub fn set(&mut self, index: usize, value: bool) {
+        if value {
+            self.set_at(index);
+        } else {
+            self.clear_at(index);
+        }
+    }
+
+    pub fn count_ones(&self) -> usize {
+        let ConstBitVec(bit_vec) = self;
+        bit_vec.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    // true if no bit is set
+    pub fn none(&self) -> bool {
+        let ConstBitVec(bit_vec) = self;
+        bit_vec.iter().all(|w| *w == T::ZERO)
+    }
+
+    pub fn iter_ones(&self) -> impl Iterator<Item=usize> + '_ {
+        let ConstBitVec(bit_vec) = self;
+        bit_vec.iter().enumerate().flat_map(|(i, word)| {
+            let mut word = *word;
+            std::iter::from_fn(move || {
+                if word == T::ZERO {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word = word & !T::ONE.shl(bit);
+                Some(i * T::BITS + bit)
+            })
+        })
+    }
+
+    fn combine(&self, other: &Self, op: impl Fn(T, T) -> T) -> Self {
+        let (ConstBitVec(a), ConstBitVec(b)) = (self, other);
+        ConstBitVec(std::array::from_fn(|i| op(a[i], b[i])))
     }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a | b)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a & b)
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a & !b)
+    }
+
+}
+
+// the bits in index order, the lowest index first
+impl<const S: usize, T: Word> Debug for ConstBitVec<S, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for index in 0..Self::capacity() {
+            write!(f, "{}", if self.is_set(index) { '1' } else { '0' })?;
+        }
+        Ok(())
+    }
+}
+
+impl<const S: usize, T: Word> FromIterator<usize> for ConstBitVec<S, T> {
+    fn from_iter<I: IntoIterator<Item=usize>>(iter: I) -> Self {
+        let mut result = Self::default();
+        for index in iter {
+            result.set_at(index);
+        }
+        result
+    }
+}
+
+// a growable bitset, bits beyond len are treated as cleared
+#[derive(Clone, Default)]
+pub struct BitVec {
+    words: Vec<u64>,
+    len: usize,
 }
 
-impl<const S: usize, T> ConstBitVec<S, T>
-    where T: From<usize> + PartialEq + Copy + Default + BitOr<Output=T> + BitAnd<Output=T> {
+const WORD_BITS: usize = u64::BITS as usize;
+
+impl BitVec {
+
+    pub fn new() -> BitVec {
+        BitVec::default()
+    }
+
+    pub fn with_len(len: usize) -> BitVec {
+        BitVec {
+            words: vec![0; len.div_ceil(WORD_BITS)],
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // true if no bit is set, also for a non empty vector
+    pub fn none(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    fn grow(&mut self, len: usize) {
+        if len > self.len {
+            self.len = len;
+            self.words.resize(len.div_ceil(WORD_BITS), 0);
+        }
+    }
+
+    pub fn is_set(&self, index: usize) -> bool {
+        self.words.get(index / WORD_BITS)
+            .is_some_and(|w| w & (1 << (index % WORD_BITS)) != 0)
+    }
+
+    pub fn set_at(&mut self, index: usize) {
+        self.grow(index + 1);
+        self.words[index / WORD_BITS] |= 1 << (index % WORD_BITS);
+    }
+
+    pub fn clear_at(&mut self, index: usize) {
+        if let Some(word) = self.words.get_mut(index / WORD_BITS) {
+            *word &= !(1 << (index % WORD_BITS));
+        }
+    }
 
     pub fn set(&mut self, index: usize, value: bool) {
         if value {
@@ -72,6 +231,190 @@ impl<const S: usize, T> ConstBitVec<S, T>
         }
     }
 
+    pub fn clear(&mut self) {
+        self.words.iter_mut().for_each(|w| *w = 0);
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn iter_ones(&self) -> impl Iterator<Item=usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, word)| {
+            let mut word = *word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(i * WORD_BITS + bit)
+            })
+        })
+    }
+
+    fn combine(&self, other: &BitVec, op: impl Fn(u64, u64) -> u64) -> BitVec {
+        let len = max(self.len, other.len);
+        let words = (0..len.div_ceil(WORD_BITS))
+            .map(|i| op(self.words.get(i).copied().unwrap_or(0), other.words.get(i).copied().unwrap_or(0)))
+            .collect();
+
+        BitVec {
+            words,
+            len,
+        }
+    }
+
+}
+
+impl PartialEq for BitVec {
+    fn eq(&self, other: &Self) -> bool {
+        let words = max(self.words.len(), other.words.len());
+        self.len == other.len
+            && (0..words).all(|i| self.words.get(i).unwrap_or(&0) == other.words.get(i).unwrap_or(&0))
+    }
+}
+
+impl Eq for BitVec {}
+
+impl Debug for BitVec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter_ones()).finish()
+    }
+}
+
+impl FromIterator<usize> for BitVec {
+    fn from_iter<I: IntoIterator<Item=usize>>(iter: I) -> Self {
+        let mut result = BitVec::new();
+        for index in iter {
+            result.set_at(index);
+        }
+        result
+    }
+}
+
+impl BitAnd for &BitVec {
+    type Output = BitVec;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, |a, b| a & b)
+    }
+}
+
+impl BitOr for &BitVec {
+    type Output = BitVec;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, |a, b| a | b)
+    }
 }
 
-pub struct BitVec(Vec<u64>);
+impl BitXor for &BitVec {
+    type Output = BitVec;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, |a, b| a ^ b)
+    }
+}
+
+// only the first len bits are flipped
+impl Not for &BitVec {
+    type Output = BitVec;
+
+    fn not(self) -> Self::Output {
+        let mut words: Vec<u64> = self.words.iter().map(|w| !w).collect();
+
+        let tail = self.len % WORD_BITS;
+        if let (Some(last), true) = (words.last_mut(), tail != 0) {
+            *last &= (1 << tail) - 1;
+        }
+
+        BitVec {
+            words,
+            len: self.len,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_bit_vec() {
+        let mut bv = BitVec::new();
+        bv.set_at(3);
+        bv.set_at(70);
+        bv.set(64, true);
+        bv.set(3, false);
+        bv.clear_at(1000);
+
+        assert_eq!(71, bv.len());
+        assert!(bv.is_set(70));
+        assert!(!bv.is_set(3));
+        assert!(!bv.is_set(1000));
+        assert_eq!(2, bv.count_ones());
+        assert_eq!(vec![64, 70], bv.iter_ones().collect::<Vec<_>>());
+        assert_eq!("{64, 70}", format!("{:?}", bv));
+
+        bv.clear();
+        assert!(bv.none());
+        assert!(!bv.is_empty());
+        assert_eq!(BitVec::with_len(71), bv);
+        assert_ne!(BitVec::new(), bv);
+
+        let zeros = BitVec::with_len(10);
+        assert!(zeros.none() && !zeros.is_empty());
+        assert!(BitVec::new().is_empty());
+    }
+
+    #[test]
+    fn test_bit_ops() {
+        let a: BitVec = [1, 2, 65].into_iter().collect();
+        let b: BitVec = [2, 3].into_iter().collect();
+
+        assert_eq!(vec![2], (&a & &b).iter_ones().collect::<Vec<_>>());
+        assert_eq!(vec![1, 2, 3, 65], (&a | &b).iter_ones().collect::<Vec<_>>());
+        assert_eq!(vec![1, 3, 65], (&a ^ &b).iter_ones().collect::<Vec<_>>());
+        assert_eq!(vec![0, 1], (!&b).iter_ones().collect::<Vec<_>>());
+        assert_eq!(63, (!&a).count_ones());
+    }
+
+    #[test]
+    fn test_const_bit_vec() {
+        let mut bv: ConstBitVec<2, u8> = ConstBitVec::new();
+        bv.set_at(0);
+        bv.set_at(7);
+        bv.set(9, true);
+        bv.set(15, true);
+        bv.clear_at(15);
+
+        assert_eq!(16, ConstBitVec::<2, u8>::capacity());
+        assert!(bv.is_set(7) && bv.is_set(9));
+        assert!(!bv.is_set(8) && !bv.is_set(15));
+        assert_eq!(3, bv.count_ones());
+        assert_eq!(vec![0, 7, 9], bv.iter_ones().collect::<Vec<_>>());
+        assert_eq!("1000000101000000", format!("{:?}", bv));
+
+        let wide: ConstBitVec<1, u128> = [0, 127].into_iter().collect();
+        assert_eq!(vec![0, 127], wide.iter_ones().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_const_set_ops() {
+        let a: ConstBitVec<2, u64> = [1, 2, 100].into_iter().collect();
+        let b: ConstBitVec<2, u64> = [2, 3].into_iter().collect();
+
+        assert_eq!(vec![1, 2, 3, 100], a.union(&b).iter_ones().collect::<Vec<_>>());
+        assert_eq!(vec![2], a.intersection(&b).iter_ones().collect::<Vec<_>>());
+        assert_eq!(vec![1, 100], a.difference(&b).iter_ones().collect::<Vec<_>>());
+        assert!(b.difference(&b).none());
+
+        let same_as_a: ConstBitVec<2, u64> = [100, 2, 1].into_iter().collect();
+        let seen: HashSet<ConstBitVec<2, u64>> = [a, b, same_as_a].into_iter().collect();
+        assert_eq!(2, seen.len());
+    }
+
+}
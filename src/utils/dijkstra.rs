@@ -0,0 +1,190 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use crate::utils::bfs::Graph;
+
+// the cost closure returns the cost of moving between two properties, None if the move is not allowed
+pub struct Dijkstra<'a, C, G>
+    where C: Fn(&G::Property, &G::Property) -> Option<u64>,
+          G: Graph,
+{
+    graph: &'a G,
+    cost: C,
+}
+
+pub struct AStar<'a, C, H, G>
+    where C: Fn(&G::Property, &G::Property) -> Option<u64>,
+          H: Fn(&G::Position) -> u64,
+          G: Graph,
+{
+    graph: &'a G,
+    cost: C,
+    heuristic: H,
+}
+
+pub fn dijkstra<G, C>(graph: &G, cost: C, start_pos: &G::Position) -> HashMap<G::Position, u64>
+    where C: Fn(&G::Property, &G::Property) -> Option<u64>,
+          G: Graph,
+{
+    Dijkstra::new(graph, cost).run(start_pos)
+}
+
+pub fn a_star<G, C, H>(graph: &G, cost: C, heuristic: H, start_pos: &G::Position, end_pos: &G::Position) -> Option<u64>
+    where C: Fn(&G::Property, &G::Property) -> Option<u64>,
+          H: Fn(&G::Position) -> u64,
+          G: Graph,
+{
+    AStar::new(graph, cost, heuristic).run(start_pos, end_pos)
+}
+
+struct State<P> {
+    estimate: u64,
+    dist: u64,
+    pos: P,
+}
+
+impl<P> PartialEq for State<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<P> Eq for State<P> {}
+
+impl<P> PartialOrd for State<P> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P> Ord for State<P> {
+    // reversed, so the BinaryHeap pops the lowest estimate first
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.cmp(&self.estimate)
+            .then_with(|| other.dist.cmp(&self.dist))
+    }
+}
+
+// settles positions in order of dist + heuristic, stops as soon as end_pos is settled
+fn search<G, C, H>(graph: &G, cost: &C, heuristic: &H, start_pos: &G::Position, end_pos: Option<&G::Position>) -> HashMap<G::Position, u64>
+    where C: Fn(&G::Property, &G::Property) -> Option<u64>,
+          H: Fn(&G::Position) -> u64,
+          G: Graph,
+{
+    let mut frontier: BinaryHeap<State<G::Position>> = BinaryHeap::new();
+    frontier.push(State { estimate: heuristic(start_pos), dist: 0, pos: start_pos.clone() });
+
+    let mut dists: HashMap<G::Position, u64> = HashMap::new();
+
+    while let Some(State { dist, pos: cur, .. }) = frontier.pop() {
+
+        if dists.contains_key(&cur) {
+            continue;
+        }
+
+        dists.insert(cur.clone(), dist);
+
+        if end_pos == Some(&cur) {
+            break;
+        }
+
+        let cur_property = graph.get_property(&cur);
+
+        for next in graph.get_surroundings(&cur) {
+            if dists.contains_key(&next) {
+                continue;
+            }
+
+            let Some(step) = cost(cur_property, graph.get_property(&next)) else {
+                continue;
+            };
+
+            let next_dist = dist + step;
+            frontier.push(State { estimate: next_dist + heuristic(&next), dist: next_dist, pos: next });
+        }
+    }
+
+    dists
+}
+
+impl<'a, C, G> Dijkstra<'a, C, G>
+    where C: Fn(&G::Property, &G::Property) -> Option<u64>,
+          G: Graph,
+{
+
+    pub fn new(graph: &'a G, cost: C) -> Dijkstra<'a, C, G> {
+        Dijkstra {
+            graph,
+            cost,
+        }
+    }
+
+    pub fn run(&self, start_pos: &G::Position) -> HashMap<G::Position, u64> {
+        search(self.graph, &self.cost, &|_: &G::Position| 0, start_pos, None)
+    }
+
+    pub fn run_to(&self, start_pos: &G::Position, end_pos: &G::Position) -> Option<u64> {
+        search(self.graph, &self.cost, &|_: &G::Position| 0, start_pos, Some(end_pos))
+            .get(end_pos).copied()
+    }
+
+}
+
+impl<'a, C, H, G> AStar<'a, C, H, G>
+    where C: Fn(&G::Property, &G::Property) -> Option<u64>,
+          H: Fn(&G::Position) -> u64,
+          G: Graph,
+{
+
+    // settled positions are never reopened, so the heuristic has to be consistent: it may not
+    // overestimate the remaining cost to end_pos, and may not drop by more than the cost of any single move
+    pub fn new(graph: &'a G, cost: C, heuristic: H) -> AStar<'a, C, H, G> {
+        AStar {
+            graph,
+            cost,
+            heuristic,
+        }
+    }
+
+    pub fn run(&self, start_pos: &G::Position, end_pos: &G::Position) -> Option<u64> {
+        search(self.graph, &self.cost, &self.heuristic, start_pos, Some(end_pos))
+            .get(end_pos).copied()
+    }
+
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::utils::Error;
+    use crate::utils::grid::Grid;
+    use crate::utils::vec2::Vec2;
+
+    static RISKS: &str = "1163751\n1381373\n2136511\n3694931\n7463417\n";
+
+    fn enter_cost(_: &u32, next: &u32) -> Option<u64> {
+        (*next != 0).then_some(*next as u64)
+    }
+
+    #[test]
+    fn test_weighted_paths() {
+        let mut grid: Grid<u32> = Grid::parse_with(RISKS, |_, c| c.to_digit(10).ok_or_else(|| Error::cannot_parse(&c))).unwrap();
+        let end_pos = Vec2(6, 4);
+
+        let dists = dijkstra(&grid, enter_cost, &Vec2(0, 0));
+        assert_eq!(Some(&0), dists.get(&Vec2(0, 0)));
+        assert_eq!(Some(&1), dists.get(&Vec2(1, 0)));
+        assert_eq!(Some(&3), dists.get(&Vec2(0, 2)));
+
+        let manhattan = |Vec2(x, y): &Vec2<usize>| ((end_pos.0 - x) + (end_pos.1 - y)) as u64;
+        let shortest = dists[&end_pos];
+        assert_eq!(Some(shortest), a_star(&grid, enter_cost, manhattan, &Vec2(0, 0), &end_pos));
+        assert_eq!(Some(shortest), Dijkstra::new(&grid, enter_cost).run_to(&Vec2(0, 0), &end_pos));
+
+        // walls around the end position
+        grid[&Vec2(5, 4)] = 0;
+        grid[&Vec2(6, 3)] = 0;
+        assert_eq!(None, a_star(&grid, enter_cost, manhattan, &Vec2(0, 0), &end_pos));
+    }
+
+}
@@ -0,0 +1,244 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use crate::utils::Error;
+use crate::utils::bfs::Graph;
+
+// positions in the order a depth first search visits them
+pub fn dfs<G: Graph>(graph: &G, start_pos: &G::Position) -> Vec<G::Position> {
+    let mut visited: HashSet<G::Position> = HashSet::new();
+    let mut order = Vec::new();
+    let mut stack = vec![start_pos.clone()];
+
+    while let Some(cur) = stack.pop() {
+        if !visited.insert(cur.clone()) {
+            continue;
+        }
+
+        // reversed, so the first surrounding is visited first
+        for next in graph.get_surroundings(&cur).into_iter().rev() {
+            if !visited.contains(&next) {
+                stack.push(next);
+            }
+        }
+
+        order.push(cur);
+    }
+
+    order
+}
+
+// labels every position with the index of its component, positions are connected if filter allows the move
+pub fn connected_components<G, F>(graph: &G, positions: impl IntoIterator<Item=G::Position>, filter: F) -> HashMap<G::Position, usize>
+    where F: Fn(&G::Property, &G::Property) -> bool,
+          G: Graph,
+{
+    let mut labels: HashMap<G::Position, usize> = HashMap::new();
+    let mut component = 0;
+
+    for start_pos in positions {
+        if labels.contains_key(&start_pos) {
+            continue;
+        }
+
+        let mut stack = vec![start_pos];
+        while let Some(cur) = stack.pop() {
+            if labels.contains_key(&cur) {
+                continue;
+            }
+
+            let cur_property = graph.get_property(&cur);
+            for next in graph.get_surroundings(&cur) {
+                if !labels.contains_key(&next) && filter(cur_property, graph.get_property(&next)) {
+                    stack.push(next);
+                }
+            }
+
+            labels.insert(cur, component);
+        }
+
+        component += 1;
+    }
+
+    labels
+}
+
+// orders the positions so that every position comes after all positions in its surroundings,
+// surroundings outside of the given positions are ignored
+pub fn topological_sort<G: Graph>(graph: &G, positions: impl IntoIterator<Item=G::Position>) -> Result<Vec<G::Position>, Error> {
+    let mut included: HashSet<G::Position> = HashSet::new();
+    let positions: Vec<G::Position> = positions.into_iter()
+        .filter(|pos| included.insert(pos.clone()))
+        .collect();
+
+    let mut dependants: HashMap<G::Position, Vec<G::Position>> = HashMap::new();
+    let mut missing: HashMap<G::Position, usize> = HashMap::new();
+
+    for pos in positions.iter() {
+        let dependencies: Vec<G::Position> = graph.get_surroundings(pos).into_iter()
+            .filter(|dependency| included.contains(dependency))
+            .collect();
+        missing.insert(pos.clone(), dependencies.len());
+
+        for dependency in dependencies {
+            dependants.entry(dependency).or_default().push(pos.clone());
+        }
+    }
+
+    let mut ready: VecDeque<G::Position> = positions.iter()
+        .filter(|pos| missing[*pos] == 0)
+        .cloned()
+        .collect();
+    let mut order = Vec::with_capacity(positions.len());
+
+    while let Some(cur) = ready.pop_front() {
+        for dependant in dependants.get(&cur).into_iter().flatten() {
+            let Some(count) = missing.get_mut(dependant) else {
+                continue;
+            };
+            *count -= 1;
+            if *count == 0 {
+                ready.push_back(dependant.clone());
+            }
+        }
+        order.push(cur);
+    }
+
+    if order.len() != positions.len() {
+        return Err(Error::invalid_state(format!("graph contains a cycle through {} positions", positions.len() - order.len())));
+    }
+
+    Ok(order)
+}
+
+#[derive(Debug)]
+pub struct AllPairs<P> {
+    indices: HashMap<P, usize>,
+    dists: Vec<Vec<Option<u64>>>,
+}
+
+impl<P: Clone + Eq + Hash> AllPairs<P> {
+
+    pub fn dist(&self, from: &P, to: &P) -> Option<u64> {
+        let from = self.indices.get(from)?;
+        let to = self.indices.get(to)?;
+        self.dists[*from][*to]
+    }
+
+}
+
+// all pairs shortest distances between the given positions, meant for small graphs
+pub fn floyd_warshall<G, C>(graph: &G, positions: impl IntoIterator<Item=G::Position>, cost: C) -> AllPairs<G::Position>
+    where C: Fn(&G::Property, &G::Property) -> Option<u64>,
+          G: Graph,
+{
+    // duplicates keep the index of their first occurrence
+    let mut indices: HashMap<G::Position, usize> = HashMap::new();
+    for pos in positions {
+        let next_index = indices.len();
+        indices.entry(pos).or_insert(next_index);
+    }
+    let n = indices.len();
+    let mut dists = vec![vec![None; n]; n];
+
+    for (pos, i) in indices.iter() {
+        dists[*i][*i] = Some(0);
+
+        let property = graph.get_property(pos);
+        for next in graph.get_surroundings(pos) {
+            let Some(j) = indices.get(&next) else {
+                continue;
+            };
+            if let Some(step) = cost(property, graph.get_property(&next)) {
+                dists[*i][*j] = Some(dists[*i][*j].map_or(step, |d: u64| d.min(step)));
+            }
+        }
+    }
+
+    for k in 0..n {
+        let via = dists[k].clone();
+
+        for row in dists.iter_mut() {
+            let Some(ik) = row[k] else {
+                continue;
+            };
+            for (dist, kj) in row.iter_mut().zip(via.iter()) {
+                if let Some(kj) = kj {
+                    if dist.is_none_or(|d| ik + kj < d) {
+                        *dist = Some(ik + kj);
+                    }
+                }
+            }
+        }
+    }
+
+    AllPairs {
+        indices,
+        dists,
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::utils::grid::Grid;
+    use crate::utils::vec2::Vec2;
+
+    // each node lists the nodes it points to
+    struct Nodes(Vec<Vec<usize>>);
+
+    impl Graph for Nodes {
+        type Position = usize;
+        type Property = ();
+
+        fn get_property(&self, _: &Self::Position) -> &Self::Property {
+            &()
+        }
+
+        fn get_surroundings(&self, pos: &Self::Position) -> Vec<Self::Position> {
+            let Nodes(edges) = self;
+            edges[*pos].clone()
+        }
+    }
+
+    #[test]
+    fn test_dfs_and_topological_sort() {
+        let nodes = Nodes(vec![vec![1, 2], vec![3], vec![3], vec![], vec![0]]);
+
+        assert_eq!(vec![0, 1, 3, 2], dfs(&nodes, &0));
+        assert_eq!(vec![3, 1, 2, 0, 4], topological_sort(&nodes, 0..5).unwrap());
+
+        let cyclic = Nodes(vec![vec![1], vec![2], vec![0], vec![]]);
+        assert_eq!("graph contains a cycle through 3 positions", topological_sort(&cyclic, 0..4).unwrap_err().to_string());
+
+        // edges leaving the subgraph are no dependencies
+        assert_eq!(vec![1, 2, 0], topological_sort(&nodes, [0, 1, 2, 1]).unwrap());
+    }
+
+    #[test]
+    fn test_components() {
+        let grid: Grid<char> = Grid::parse_with("aab\nbab\nbba\n", |_, c| Ok(c)).unwrap();
+
+        let labels = connected_components(&grid, grid.positions(), |cur, next| cur == next);
+        assert_eq!(4, labels.values().collect::<HashSet<_>>().len());
+        assert_eq!(labels[&Vec2(0, 0)], labels[&Vec2(1, 1)]);
+        assert_eq!(labels[&Vec2(0, 1)], labels[&Vec2(1, 2)]);
+        assert_ne!(labels[&Vec2(2, 0)], labels[&Vec2(2, 2)]);
+    }
+
+    #[test]
+    fn test_floyd_warshall() {
+        let nodes = Nodes(vec![vec![1, 2], vec![3], vec![3], vec![], vec![0]]);
+
+        let all_pairs = floyd_warshall(&nodes, 0..5, |_, _| Some(1));
+        assert_eq!(Some(3), all_pairs.dist(&4, &3));
+        assert_eq!(Some(0), all_pairs.dist(&2, &2));
+        assert_eq!(None, all_pairs.dist(&3, &0));
+        assert_eq!(None, all_pairs.dist(&0, &7));
+
+        let all_pairs = floyd_warshall(&nodes, [0, 1, 0, 3, 1], |_, _| Some(1));
+        assert_eq!(Some(2), all_pairs.dist(&0, &3));
+        assert_eq!(None, all_pairs.dist(&0, &2));
+    }
+
+}
@@ -0,0 +1,273 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+use crate::utils::{Error, Surroundings};
+use crate::utils::bfs::Graph;
+use crate::utils::vec2::Vec2;
+
+pub type GridPos = Vec2<usize>;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+
+    // a grid without columns or rows is empty in both directions
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T> {
+        let (width, height) = if width == 0 || height == 0 { (0, 0) } else { (width, height) };
+
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+}
+
+impl<T> Grid<T> {
+
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Result<Grid<T>, Error> {
+        if width == 0 || !cells.len().is_multiple_of(width) {
+            return Err(Error::invalid_state(format!("cannot split {} cells into rows of {}", cells.len(), width)));
+        }
+
+        Ok(Grid {
+            width,
+            height: cells.len() / width,
+            cells,
+        })
+    }
+
+    // parses a character map, all lines need to have the same width
+    pub fn parse_with(s: &str, mut mapper: impl FnMut(&GridPos, char) -> Result<T, Error>) -> Result<Grid<T>, Error> {
+        let mut width = None;
+        let mut cells = Vec::new();
+
+        for (y, line) in s.lines().map(str::trim_end).enumerate() {
+            let mut line_width = 0;
+
+            for (x, c) in line.chars().enumerate() {
+                let cell = mapper(&Vec2(x, y), c).map_err(|e| e.with_line(y + 1).with_column(x + 1))?;
+                cells.push(cell);
+                line_width += 1;
+            }
+
+            match width {
+                None => width = Some(line_width),
+                Some(width) if width != line_width => {
+                    return Err(Error::parse(format!("expected {} columns but got {}", width, line_width)).with_line(y + 1));
+                }
+                _ => {}
+            }
+        }
+
+        match width {
+            Some(width) if width > 0 => Grid::from_vec(width, cells),
+            _ => Err(Error::parse("empty grid")),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn size(&self) -> GridPos {
+        Vec2(self.width, self.height)
+    }
+
+    pub fn contains(&self, pos: &GridPos) -> bool {
+        let Vec2(x, y) = pos;
+        *x < self.width && *y < self.height
+    }
+
+    fn index_of(&self, pos: &GridPos) -> Option<usize> {
+        let Vec2(x, y) = pos;
+        self.contains(pos).then(|| y * self.width + x)
+    }
+
+    pub fn get(&self, pos: &GridPos) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: &GridPos) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item=&[T]> {
+        (0..self.height).map(|y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item=&T> + ExactSizeIterator {
+        let height = if x < self.width { self.height } else { 0 };
+        (0..height).map(move |y| &self.cells[y * self.width + x])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item=impl Iterator<Item=&T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item=GridPos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Vec2(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=(GridPos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn map<K>(&self, mapper: impl Fn(&T) -> K) -> Grid<K> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(mapper).collect(),
+        }
+    }
+
+    pub fn neighbours(&self, pos: &GridPos) -> Vec<GridPos> {
+        let signed = pos.map(|v| *v as isize);
+
+        Surroundings::<4>::get_surroundings(&signed).iter()
+            .filter(|Vec2(x, y)| *x >= 0 && *y >= 0)
+            .map(|p| p.map(|v| *v as usize))
+            .filter(|p| self.contains(p))
+            .collect()
+    }
+
+    pub fn render(&self, mapper: impl Fn(&T) -> char) -> String {
+        let mut result = String::with_capacity((self.width + 1) * self.height);
+
+        for row in self.rows() {
+            result.extend(row.iter().map(&mapper));
+            result.push('\n');
+        }
+
+        result
+    }
+
+}
+
+impl<T> Index<&GridPos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: &GridPos) -> &Self::Output {
+        self.get(pos).unwrap_or_else(|| panic!("{:?} is outside of the grid {:?}", pos, self.size()))
+    }
+}
+
+impl<T> IndexMut<&GridPos> for Grid<T> {
+    fn index_mut(&mut self, pos: &GridPos) -> &mut Self::Output {
+        let size = self.size();
+        self.get_mut(pos).unwrap_or_else(|| panic!("{:?} is outside of the grid {:?}", pos, size))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Graph for Grid<T> {
+    type Position = GridPos;
+    type Property = T;
+
+    fn get_property(&self, pos: &Self::Position) -> &Self::Property {
+        &self[pos]
+    }
+
+    fn get_surroundings(&self, pos: &Self::Position) -> Vec<Self::Position> {
+        self.neighbours(pos)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::utils::bfs::{bfs, filtered_bfs};
+
+    static INPUT: &str = "123\n456\n";
+
+    fn parse_digits(s: &str) -> Result<Grid<u32>, Error> {
+        Grid::parse_with(s, |_, c| c.to_digit(10).ok_or_else(|| Error::cannot_parse(&c)))
+    }
+
+    #[test]
+    fn test_parse_and_render() {
+        let grid = parse_digits(INPUT).unwrap();
+
+        assert_eq!(Vec2(3, 2), grid.size());
+        assert_eq!(Some(&6), grid.get(&Vec2(2, 1)));
+        assert_eq!(None, grid.get(&Vec2(3, 0)));
+        assert_eq!(INPUT, grid.to_string());
+        assert_eq!("..#\n###\n", grid.render(|v| if *v > 2 { '#' } else { '.' }));
+
+        assert_eq!("line 2, column 2: cannot parse x", parse_digits("12\n3x\n").unwrap_err().to_string());
+        assert_eq!("line 2: expected 2 columns but got 3", parse_digits("12\n345\n").unwrap_err().to_string());
+        assert!(parse_digits("").is_err());
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        for (width, height) in [(0, 3), (3, 0), (0, 0)] {
+            let grid = Grid::filled(width, height, 0);
+
+            assert_eq!(Vec2(0, 0), grid.size());
+            assert_eq!(0, grid.rows().count());
+            assert_eq!(0, grid.positions().count());
+            assert_eq!("", grid.to_string());
+            assert_eq!("", grid.render(|_| '#'));
+        }
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = parse_digits(INPUT).unwrap();
+
+        assert_eq!(Some(&[4, 5, 6][..]), grid.row(1));
+        assert_eq!(None, grid.row(2));
+        assert_eq!(vec![2, 5], grid.column(1).copied().collect::<Vec<_>>());
+        assert_eq!(0, grid.column(3).count());
+
+        let column_sums: Vec<u32> = grid.columns().map(|c| c.sum()).collect();
+        assert_eq!(vec![5, 7, 9], column_sums);
+
+        let (pos, value) = grid.iter().last().unwrap();
+        assert_eq!((Vec2(2, 1), &6), (pos, value));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let mut grid = parse_digits(INPUT).unwrap();
+
+        let mut neighbours = grid.neighbours(&Vec2(0, 0));
+        neighbours.sort();
+        assert_eq!(vec![Vec2(0, 1), Vec2(1, 0)], neighbours);
+        assert_eq!(3, grid.neighbours(&Vec2(1, 1)).len());
+
+        grid[&Vec2(1, 0)] = 0;
+        grid[&Vec2(1, 1)] = 0;
+        let dists = filtered_bfs(&grid, |_, next| *next != 0, &Vec2(0, 0));
+        assert_eq!(2, dists.len());
+        assert_eq!(3, bfs(&grid, &Vec2(0, 0))[&Vec2(2, 1)]);
+    }
+
+}
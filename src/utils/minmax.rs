@@ -5,13 +5,10 @@ pub trait MinMax<T> {
 
 impl<'a, T: 'a + PartialOrd, I: Iterator<Item=&'a T>> MinMax<&'a T> for I {
     fn min_max(mut self) -> Option<(&'a T, &'a T)> {
-        let start = self.next();
-        if start.is_none() {
-            return None;
-        }
+        let start = self.next()?;
 
-        let mut min = start.unwrap();
-        let mut max = start.unwrap();
+        let mut min = start;
+        let mut max = start;
 
         for i in self {
             if i < min {
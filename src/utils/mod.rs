@@ -1,5 +1,7 @@
-use std::fmt::Debug;
+use std::fmt::{Debug, Display, Formatter};
+use std::io;
 use std::num::ParseIntError;
+use std::str::FromStr;
 
 pub mod vec2;
 pub mod ranges;
@@ -10,26 +12,231 @@ pub mod ringbuf;
 pub mod minmax;
 pub mod bfs;
 pub mod turtle;
+pub mod parser;
+pub mod grid;
+pub mod dijkstra;
+pub mod graph;
+pub mod boxes;
+pub mod bounding_box;
 
 #[derive(Debug)]
-pub struct Error(pub String);
+pub enum Error {
+    Parse {
+        message: String,
+        line: Option<usize>,
+        column: Option<usize>,
+    },
+    InvalidState(String),
+    Io {
+        context: String,
+        source: io::Error,
+    },
+}
 
 impl Error {
-    pub fn new(message: &impl ToString) -> Error {
-        Error(message.to_string())
+    pub fn parse(message: impl ToString) -> Error {
+        Error::Parse {
+            message: message.to_string(),
+            line: None,
+            column: None,
+        }
     }
 
     pub fn cannot_parse(original: &(impl ToString + ?Sized)) -> Error {
-        Error::new(&format!("cannot parse {}", original.to_string()))
+        Error::parse(format!("cannot parse {}", original.to_string()))
+    }
+
+    pub fn cannot_parse_at(s: &str, fragment: &str) -> Error {
+        Error::cannot_parse(fragment).at(s, fragment)
+    }
+
+    pub fn invalid_state(message: impl ToString) -> Error {
+        Error::InvalidState(message.to_string())
+    }
+
+    pub fn io(context: impl ToString, source: io::Error) -> Error {
+        Error::Io {
+            context: context.to_string(),
+            source,
+        }
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            Error::Parse { line, .. } => *line,
+            _ => None,
+        }
+    }
+
+    pub fn column(&self) -> Option<usize> {
+        match self {
+            Error::Parse { column, .. } => *column,
+            _ => None,
+        }
+    }
+
+    pub fn with_line(mut self, new_line: usize) -> Error {
+        if let Error::Parse { line: line @ None, .. } = &mut self {
+            *line = Some(new_line);
+        }
+        self
+    }
+
+    pub fn with_column(mut self, new_column: usize) -> Error {
+        if let Error::Parse { column: column @ None, .. } = &mut self {
+            *column = Some(new_column);
+        }
+        self
+    }
+
+    pub fn offset_lines(mut self, offset: usize) -> Error {
+        if let Error::Parse { line: Some(line), .. } = &mut self {
+            *line += offset;
+        }
+        self
+    }
+
+    pub fn at(mut self, s: &str, fragment: &str) -> Error {
+        let Some((fragment_line, fragment_column)) = locate(s, fragment) else {
+            return self;
+        };
+
+        if let Error::Parse { line, column, .. } = &mut self {
+            if line.is_none_or(|l| l == 1) {
+                *column = Some(column.map_or(fragment_column, |c| c + fragment_column - 1));
+            }
+
+            if s.contains('\n') {
+                *line = Some(line.map_or(fragment_line, |l| l + fragment_line - 1));
+            }
+        }
+
+        self
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse { message, line: Some(line), column: Some(column) } => write!(f, "line {}, column {}: {}", line, column, message),
+            Error::Parse { message, line: Some(line), column: None } => write!(f, "line {}: {}", line, message),
+            Error::Parse { message, line: None, column: Some(column) } => write!(f, "column {}: {}", column, message),
+            Error::Parse { message, .. } => write!(f, "{}", message),
+            Error::InvalidState(message) => write!(f, "{}", message),
+            Error::Io { context, source } => write!(f, "{}: {}", context, source),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
     }
 }
 
 impl From<ParseIntError> for Error {
     fn from(pie: ParseIntError) -> Self {
-        Error(pie.to_string())
+        Error::parse(pie)
     }
 }
 
+pub fn locate(s: &str, fragment: &str) -> Option<(usize, usize)> {
+    let start = s.as_ptr() as usize;
+    let offset = (fragment.as_ptr() as usize).checked_sub(start)?;
+
+    if offset + fragment.len() > s.len() {
+        return None;
+    }
+
+    let before = &s[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+    Some((line, before[line_start..].chars().count() + 1))
+}
+
+pub fn parse_at<T>(s: &str, fragment: &str) -> Result<T, Error> where T: FromStr, T::Err: Display {
+    fragment.trim().parse()
+        .map_err(|e| Error::parse(format!("cannot parse {}: {}", fragment.trim(), e)).at(s, fragment))
+}
+
+pub fn parse_lines<T>(s: &str) -> Result<Vec<T>, Error> where T: FromStr, Error: From<T::Err> {
+    s.lines()
+        .enumerate()
+        .map(|(i, line)| line.trim_end().parse().map_err(|e| Error::from(e).with_line(i + 1)))
+        .collect()
+}
+
 pub trait Surroundings<const N: usize> {
     fn get_surroundings(&self) -> [Self; N] where Self: Sized;
 }
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_locate() {
+        let input = "first line\nsecond line\nthird";
+        let second = input.lines().nth(1).unwrap();
+
+        assert_eq!(Some((1, 1)), locate(input, input));
+        assert_eq!(Some((2, 1)), locate(input, second));
+        assert_eq!(Some((2, 8)), locate(input, &second[7..]));
+        assert_eq!(Some((3, 3)), locate(input, &input[input.len() - 3..]));
+        assert_eq!(None, locate(input, "second"));
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let numbers: Vec<u32> = parse_lines::<Pair>("1,2\n3,4").unwrap().into_iter().map(|Pair(x, y)| x * 10 + y).collect();
+        assert_eq!(vec![12, 34], numbers);
+
+        let error = parse_lines::<Pair>("1,2\n3,4\n5,x").unwrap_err();
+        assert_eq!(Some(3), error.line());
+        assert_eq!(Some(3), error.column());
+        assert_eq!("line 3, column 3: cannot parse x: invalid digit found in string", error.to_string());
+    }
+
+    #[derive(Debug)]
+    struct Pair(u32, u32);
+
+    impl FromStr for Pair {
+        type Err = Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let (x, y) = s.split_once(',').ok_or_else(|| Error::cannot_parse(s))?;
+            Ok(Pair(parse_at(s, x)?, parse_at(s, y)?))
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("cannot parse x", Error::cannot_parse("x").to_string());
+        assert_eq!("line 2: cannot parse x", Error::cannot_parse("x").with_line(2).to_string());
+        assert_eq!("line 4: cannot parse x", Error::cannot_parse("x").with_line(2).offset_lines(2).to_string());
+        assert_eq!("broken", Error::invalid_state("broken").to_string());
+        assert_eq!("line 1, column 3: cannot parse x", Error::cannot_parse_at("a x", &"a x"[2..]).with_line(1).to_string());
+    }
+
+    #[test]
+    fn test_nested_at() {
+        let input = "a 1-2\nb 3-x";
+        let line = input.lines().nth(1).unwrap();
+        let range = &line[2..];
+
+        let error = Error::cannot_parse_at(range, &range[2..]);
+        assert_eq!(Some(3), error.column());
+
+        let error = error.at(line, range);
+        assert_eq!((None, Some(5)), (error.line(), error.column()));
+
+        let error = error.at(input, line);
+        assert_eq!((Some(2), Some(5)), (error.line(), error.column()));
+    }
+
+}
@@ -1,4 +1,4 @@
-use std::ops::{Add, Sub};
+use std::ops::{Add, Div, Mul, Rem, Sub};
 
 pub trait Decrement {
     type Output;
@@ -20,18 +20,26 @@ pub trait One {
     fn one() -> Self;
 }
 
-impl<T: From<i8>> Zero for T {
-    fn zero() -> Self {
-        (0 as i8).into()
-    }
-}
+macro_rules! impl_zero_one {
+    ($zero:literal, $one:literal, $($t:ty),*) => {
+        $(
+            impl Zero for $t {
+                fn zero() -> Self {
+                    $zero
+                }
+            }
 
-impl<T: From<i8>> One for T {
-    fn one() -> Self {
-        (1 as i8).into()
-    }
+            impl One for $t {
+                fn one() -> Self {
+                    $one
+                }
+            }
+        )*
+    };
 }
 
+impl_zero_one!(0, 1, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_zero_one!(0.0, 1.0, f32, f64);
 
 impl<T: One + Add<T, Output=T>> Increment for T{
     type Output = T;
@@ -48,3 +56,150 @@ impl<T: One + Sub<T, Output=T>> Decrement for T{
         self - T::one()
     }
 }
+
+pub trait Integer: Copy + Ord + Zero + One
+    + Add<Output=Self> + Sub<Output=Self> + Mul<Output=Self> + Div<Output=Self> + Rem<Output=Self> {
+}
+
+impl<T> Integer for T
+    where T: Copy + Ord + Zero + One
+        + Add<Output=T> + Sub<Output=T> + Mul<Output=T> + Div<Output=T> + Rem<Output=T> {
+}
+
+// the remainder in 0..|m|, also for negative values
+pub fn rem_euclid<T: Integer>(a: T, m: T) -> T {
+    let r = a % m;
+    if r >= T::zero() {
+        r
+    } else if m > T::zero() {
+        r + m
+    } else {
+        r - m
+    }
+}
+
+fn abs<T: Integer>(a: T) -> T {
+    if a < T::zero() { T::zero() - a } else { a }
+}
+
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (abs(a), abs(b));
+    while b != T::zero() {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::zero() || b == T::zero() {
+        return T::zero();
+    }
+    abs(a) / gcd(a, b) * abs(b)
+}
+
+// returns (g, x, y) with a * x + b * y = g = gcd(a, b), x and y can be negative so T has to be signed
+pub fn extended_gcd<T: Integer>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+
+    while r != T::zero() {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < T::zero() {
+        (T::zero() - old_r, T::zero() - old_x, T::zero() - old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+// the x in 0..m with a * x = 1 mod m, None if a and m are not coprime, T has to be signed
+pub fn mod_inverse<T: Integer>(a: T, m: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(rem_euclid(a, m), m);
+    (g == T::one()).then(|| rem_euclid(x, m))
+}
+
+// base^exp mod m, intermediate products have to fit into T
+pub fn mod_pow<T: Integer>(base: T, mut exp: T, m: T) -> T {
+    let two = T::one() + T::one();
+    let mut base = rem_euclid(base, m);
+    let mut result = rem_euclid(T::one(), m);
+
+    while exp > T::zero() {
+        if exp % two == T::one() {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp = exp / two;
+    }
+
+    result
+}
+
+// solves x = r mod m for all (r, m), returning (x, lcm of all m), the moduli don't need to be coprime, T has to be signed
+pub fn chinese_remainder<T: Integer>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let mut result = (T::zero(), T::one());
+
+    for (r, m) in congruences.iter().copied() {
+        let (x, n) = result;
+        let (g, p, _) = extended_gcd(n, m);
+
+        let diff = r - x;
+        if diff % g != T::zero() {
+            return None;
+        }
+
+        let modulus = n / g * m;
+        let step = rem_euclid(diff / g * p, m / g);
+        result = (rem_euclid(x + n * step, modulus), modulus);
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(6, gcd(54, 24));
+        assert_eq!(6, gcd(-54, 24));
+        assert_eq!(5u64, gcd(0, 5));
+        assert_eq!(216, lcm(54, 24));
+        assert_eq!(9699690u64, [2, 3, 5, 7, 11, 13, 17, 19].into_iter().fold(1, lcm));
+        assert_eq!(0, lcm(0, 3));
+    }
+
+    #[test]
+    fn test_modular() {
+        assert_eq!(2, rem_euclid(-7, 3));
+        assert_eq!(2, rem_euclid(-7, -3));
+        assert_eq!(1usize, rem_euclid(7, 3));
+
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(2, g);
+        assert_eq!(2, 240 * x + 46 * y);
+
+        assert_eq!(Some(4), mod_inverse(3, 11));
+        assert_eq!(Some(7), mod_inverse(-3, 11));
+        assert_eq!(None, mod_inverse(4, 8));
+
+        assert_eq!(445, mod_pow(4, 13, 497));
+        assert_eq!(1u64, mod_pow(7, 0, 13));
+    }
+
+    #[test]
+    fn test_chinese_remainder() {
+        assert_eq!(Some((23, 105)), chinese_remainder(&[(2, 3), (3, 5), (2, 7)]));
+        assert_eq!(Some((10, 12)), chinese_remainder(&[(4, 6), (2, 4)]));
+        assert_eq!(None, chinese_remainder(&[(1, 4), (2, 6)]));
+        assert_eq!(Some((0, 1)), chinese_remainder::<i64>(&[]));
+    }
+
+}
@@ -0,0 +1,299 @@
+use std::fmt::Display;
+use std::str::FromStr;
+use crate::utils::{parse_at, Error};
+
+// a cursor over a sub slice of `source`, errors are located relative to `source`
+#[derive(Debug, Clone, Copy)]
+pub struct Parser<'a> {
+    source: &'a str,
+    rest: &'a str,
+}
+
+pub fn parse_with<'a, T>(s: &'a str, f: impl FnOnce(&mut Parser<'a>) -> Result<T, Error>) -> Result<T, Error> {
+    let mut parser = Parser::new(s);
+    let result = f(&mut parser)?;
+    parser.end()?;
+    Ok(result)
+}
+
+impl<'a> Parser<'a> {
+
+    pub fn new(source: &'a str) -> Parser<'a> {
+        Parser {
+            source,
+            rest: source,
+        }
+    }
+
+    fn sub_parser(&self, rest: &'a str) -> Parser<'a> {
+        Parser {
+            source: self.source,
+            rest,
+        }
+    }
+
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    // located at the next token
+    pub fn error(&self, message: impl ToString) -> Error {
+        Error::parse(message).at(self.source, self.rest.trim_start())
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    fn advance(&mut self, len: usize) -> &'a str {
+        let (taken, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        taken
+    }
+
+    fn next_token(&self) -> &'a str {
+        self.rest.split_whitespace().next().unwrap_or("end of input")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest.trim().is_empty()
+    }
+
+    pub fn end(&mut self) -> Result<(), Error> {
+        self.skip_whitespace();
+        if self.rest.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error(format!("unexpected {}", self.next_token())))
+        }
+    }
+
+    pub fn try_literal(&mut self, literal: &str) -> bool {
+        let trimmed = self.rest.trim_start();
+        match trimmed.strip_prefix(literal) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn literal(&mut self, literal: &str) -> Result<(), Error> {
+        if self.try_literal(literal) {
+            Ok(())
+        } else {
+            self.skip_whitespace();
+            Err(self.error(format!("expected {} but got {}", literal.trim(), self.next_token())))
+        }
+    }
+
+    pub fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        self.skip_whitespace();
+        let len = self.rest.find(|c| !predicate(c)).unwrap_or(self.rest.len());
+        self.advance(len)
+    }
+
+    pub fn token(&mut self) -> Result<&'a str, Error> {
+        match self.take_while(|c| !c.is_whitespace()) {
+            "" => Err(self.error("expected a token but got end of input")),
+            token => Ok(token),
+        }
+    }
+
+    pub fn integer<T>(&mut self) -> Result<T, Error> where T: FromStr, T::Err: Display {
+        self.skip_whitespace();
+        let sign_len = self.rest.starts_with(['-', '+']) as usize;
+        let len = self.rest[sign_len..].find(|c: char| !c.is_alphanumeric()).unwrap_or(self.rest.len() - sign_len);
+
+        if sign_len + len == 0 {
+            return Err(self.error(format!("expected an integer but got {}", self.next_token())));
+        }
+
+        let token = self.advance(sign_len + len);
+        parse_at(self.source, token)
+    }
+
+    pub fn parse_token<T>(&mut self) -> Result<T, Error> where T: FromStr, Error: From<T::Err> {
+        let token = self.token()?;
+        token.parse().map_err(|e| Error::from(e).at(self.source, token))
+    }
+
+    pub fn parse_char<T>(&mut self) -> Result<T, Error> where T: FromStr, Error: From<T::Err> {
+        self.skip_whitespace();
+        let len = self.rest.chars().next().map_or(0, char::len_utf8);
+
+        if len == 0 {
+            return Err(self.error("expected a character but got end of input"));
+        }
+
+        let fragment = self.advance(len);
+        fragment.parse().map_err(|e| Error::from(e).at(self.source, fragment))
+    }
+
+    // parses everything up to the end of the current line
+    pub fn parse<T>(&mut self) -> Result<T, Error> where T: FromStr, Error: From<T::Err> {
+        self.skip_whitespace();
+        let len = self.rest.find('\n').unwrap_or(self.rest.len());
+        let fragment = self.advance(len).trim_end();
+        fragment.parse().map_err(|e| Error::from(e).at(self.source, fragment))
+    }
+
+    pub fn optional<T>(&mut self, f: impl FnOnce(&mut Parser<'a>) -> Result<T, Error>) -> Option<T> {
+        let mut parser = *self;
+        let result = f(&mut parser).ok()?;
+        *self = parser;
+        Some(result)
+    }
+
+    pub fn separated<T>(&mut self, separator: &str, mut item: impl FnMut(&mut Parser<'a>) -> Result<T, Error>) -> Result<Vec<T>, Error> {
+        let mut result = vec![item(self)?];
+
+        while self.try_literal(separator) {
+            result.push(item(self)?);
+        }
+
+        Ok(result)
+    }
+
+    pub fn array<const N: usize, T>(&mut self, separator: &str, mut item: impl FnMut(&mut Parser<'a>) -> Result<T, Error>) -> Result<[T; N], Error> {
+        let mut items = Vec::with_capacity(N);
+
+        for i in 0..N {
+            if i > 0 {
+                self.literal(separator)?;
+            }
+            items.push(item(self)?);
+        }
+
+        let end = *self;
+        if !separator.is_empty() && self.try_literal(separator) {
+            return Err(end.error(format!("expected {} items", N)));
+        }
+
+        items.try_into().map_err(|_| end.error(format!("expected {} items", N)))
+    }
+
+    // `name: value` where value is parsed from the rest of the line
+    pub fn field<T>(&mut self, name: &str, f: impl FnOnce(&mut Parser<'a>) -> Result<T, Error>) -> Result<T, Error> {
+        self.literal(name)?;
+        self.literal(":")?;
+
+        let len = self.rest.find('\n').unwrap_or(self.rest.len());
+        let line = self.advance(len);
+        let mut value = self.sub_parser(line);
+        let result = f(&mut value)?;
+        value.end()?;
+
+        Ok(result)
+    }
+
+    pub fn lines<T>(&mut self, mut item: impl FnMut(&mut Parser<'a>) -> Result<T, Error>) -> Result<Vec<T>, Error> {
+        let mut result = Vec::new();
+
+        for line in self.rest.lines().filter(|l| !l.trim().is_empty()) {
+            let mut line_parser = self.sub_parser(line);
+            result.push(item(&mut line_parser)?);
+            line_parser.end()?;
+        }
+
+        self.rest = &self.rest[self.rest.len()..];
+        Ok(result)
+    }
+
+    // blocks of lines separated by blank lines
+    pub fn blocks<T>(&mut self, mut item: impl FnMut(&mut Parser<'a>) -> Result<T, Error>) -> Result<Vec<T>, Error> {
+        let mut result = Vec::new();
+
+        while !self.is_empty() {
+            self.skip_whitespace();
+
+            let mut len = 0;
+            for line in self.rest.split_inclusive('\n') {
+                if line.trim().is_empty() {
+                    break;
+                }
+                len += line.len();
+            }
+
+            let lines = self.advance(len);
+            let mut block = self.sub_parser(lines);
+            result.push(item(&mut block)?);
+            block.end()?;
+        }
+
+        Ok(result)
+    }
+
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_tokens() {
+        let (a, b, c) = parse_with("move 3 from -1 to x", |p| {
+            p.literal("move")?;
+            let a: u32 = p.integer()?;
+            p.literal("from")?;
+            let b: i32 = p.integer()?;
+            p.literal("to")?;
+            Ok((a, b, p.token()?))
+        }).unwrap();
+
+        assert_eq!((3, -1, "x"), (a, b, c));
+
+        let error = parse_with("move 3 to 4", |p| {
+            p.literal("move")?;
+            p.integer::<u32>()?;
+            p.literal("from")
+        }).unwrap_err();
+        assert_eq!("column 8: expected from but got to", error.to_string());
+
+        let error = parse_with("move x3", |p| { p.literal("move")?; p.integer::<u32>() }).unwrap_err();
+        assert_eq!("column 6: cannot parse x3: invalid digit found in string", error.to_string());
+
+        assert!(parse_with("1 2", |p| p.integer::<u32>()).is_err());
+        assert_eq!(None, Parser::new("abc").optional(|p| p.integer::<u32>()));
+    }
+
+    #[test]
+    fn test_lists() {
+        let items: Vec<u32> = parse_with("1, 2,3", |p| p.separated(",", Parser::integer)).unwrap();
+        assert_eq!(vec![1, 2, 3], items);
+
+        let digits: [u32; 4] = parse_with("6432", |p| p.array("", Parser::parse_char)).unwrap();
+        assert_eq!([6, 4, 3, 2], digits);
+
+        let error = parse_with("1,2,3", |p| p.array::<2, u32>(",", Parser::integer)).unwrap_err();
+        assert_eq!("column 4: expected 2 items", error.to_string());
+
+        let error = parse_with("1,2", |p| p.array::<3, u32>(",", Parser::integer)).unwrap_err();
+        assert_eq!("column 4: expected , but got end of input", error.to_string());
+    }
+
+    #[test]
+    fn test_blocks_and_fields() {
+        let input = "a: 1\nb: 2, 3\n\n\na: 4\nb: 5\n";
+
+        let blocks = parse_with(input, |p| p.blocks(|p| {
+            let a: u32 = p.field("a", Parser::integer)?;
+            let b: Vec<u32> = p.field("b", |p| p.separated(",", Parser::integer))?;
+            Ok((a, b))
+        })).unwrap();
+
+        assert_eq!(vec![(1, vec![2, 3]), (4, vec![5])], blocks);
+
+        let error = parse_with(input, |p| p.blocks(|p| {
+            p.field("a", Parser::integer::<u32>)?;
+            p.field("b", Parser::integer::<u32>)
+        })).unwrap_err();
+        assert_eq!("line 2, column 5: unexpected ,", error.to_string());
+
+        let lines = parse_with("1\n\n2\n", |p| p.lines(Parser::integer::<u32>)).unwrap();
+        assert_eq!(vec![1, 2], lines);
+    }
+
+}
@@ -1,17 +1,29 @@
-use std::ops::{RangeInclusive, Sub};
-use crate::utils::num::{Decrement, Increment};
+use std::ops::{Add, Range, RangeFrom, RangeInclusive, RangeTo, Sub};
+use crate::utils::num::{Decrement, Increment, Zero};
 
 pub trait RangeExt {
 
+    // the bounded range type left over when splitting or subtracting
+    type Piece;
+
     fn intersects(&self, other: &Self) -> bool where Self: Sized {
         self.intersect(other).is_some()
     }
 
-    fn contains_fully(&self, other: &Self) -> bool;
+    fn contains_fully(&self, other: &Self) -> bool where Self: Sized + PartialEq {
+        match self.intersect(other) {
+            Some(intersection) => &intersection == self || &intersection == other,
+            None => false,
+        }
+    }
 
     fn intersect(&self, other: &Self) -> Option<Self> where Self: Sized;
 
-    fn subtract(&self, other: &Self) -> Option<Self> where Self: Sized;
+    // the parts of self before and after other
+    fn subtract(&self, other: &Self) -> [Option<Self::Piece>; 2] where Self: Sized {
+        let (before, _, after) = self.split_off(other);
+        [before, after]
+    }
 
     fn join(&self, other: &Self) -> (Self, Option<Self>) where Self: Sized;
 
@@ -21,21 +33,14 @@ pub trait RangeExt {
         reminder
     }
 
-    fn split_off(&self, other: &Self) -> [Option<Self>; 3] where Self: Sized;
+    // the parts of self before, inside and after other, self is returned whole on its side of other if they don't intersect
+    fn split_off(&self, other: &Self) -> (Option<Self::Piece>, Option<Self>, Option<Self::Piece>) where Self: Sized;
 }
 
 impl<T> RangeExt for RangeInclusive<T>
 where T: Ord + Copy + Decrement<Output = T> + Increment<Output = T>
 {
-    fn contains_fully(&self, other: &Self) -> bool {
-        let intersection = self.intersect(other);
-        if intersection.is_none() {
-            return false;
-        }
-        let intersection = intersection.unwrap();
-
-        return &intersection == self || &intersection == other;
-    }
+    type Piece = RangeInclusive<T>;
 
     fn intersect(&self, other: &Self) -> Option<Self> {
         let max_start = std::cmp::max(self.start(), other.start());
@@ -48,21 +53,6 @@ where T: Ord + Copy + Decrement<Output = T> + Increment<Output = T>
         }
     }
 
-    fn subtract(&self, rhs: &Self) -> Option<Self> where Self: Sized {
-        match self.intersect(rhs) {
-            None => Some(self.clone()),
-            Some(intersection) => {
-                if self == &intersection {
-                    None
-                } else if self.start() == intersection.start() {
-                    Some(*intersection.end()..=*self.end())
-                } else {
-                    Some(*self.start()..=*intersection.start())
-                }
-            }
-        }
-    }
-
     // 123 456 789
     //     456
     // 123 456 789
@@ -83,35 +73,97 @@ where T: Ord + Copy + Decrement<Output = T> + Increment<Output = T>
         if self.intersects(other) {
             let min_start = std::cmp::min(self.start(), other.start());
             let max_end = std::cmp::max(self.end(), other.end());
-            return (*min_start..=*max_end, None);
+            (*min_start..=*max_end, None)
         } else {
             (self.clone(), Some(other.clone()))
         }
     }
 
-    fn split_off(&self, other: &Self) -> [Option<Self>; 3] where Self: Sized {
-        let mut result = [None, None, None];
+    fn split_off(&self, other: &Self) -> (Option<Self>, Option<Self>, Option<Self>) {
+        let Some(intersection) = self.intersect(other) else {
+            return if self.start() > other.end() {
+                (None, None, Some(self.clone()))
+            } else {
+                (Some(self.clone()), None, None)
+            };
+        };
 
-        let intersection = self.intersect(other);
+        let before = (intersection.start() != self.start())
+            .then(|| *self.start()..=intersection.start().dec());
+        let after = (intersection.end() != self.end())
+            .then(|| intersection.end().inc()..=*self.end());
 
-        if intersection.is_none() {
-            result[0] = Some(self.clone());
-            return result;
-        }
+        (before, Some(intersection), after)
+    }
+}
 
-        let intersection = intersection.unwrap();
+impl<T: Ord + Copy> RangeExt for Range<T> {
+    type Piece = Range<T>;
 
-        if intersection.start() != self.start() {
-            result[0] = Some(*self.start()..=intersection.start().dec());
-        }
+    fn intersect(&self, other: &Self) -> Option<Self> {
+        let max_start = std::cmp::max(self.start, other.start);
+        let min_end = std::cmp::min(self.end, other.end);
+
+        (max_start < min_end).then_some(max_start..min_end)
+    }
 
-        if intersection.end() != self.end() {
-            result[2] = Some(intersection.end().inc()..=*self.end());
+    fn join(&self, other: &Self) -> (Self, Option<Self>) {
+        if self.intersects(other) {
+            (std::cmp::min(self.start, other.start)..std::cmp::max(self.end, other.end), None)
+        } else {
+            (self.clone(), Some(other.clone()))
         }
+    }
 
-        result[1] = Some(intersection);
+    fn split_off(&self, other: &Self) -> (Option<Self>, Option<Self>, Option<Self>) {
+        let Some(intersection) = self.intersect(other) else {
+            return if self.start >= other.end {
+                (None, None, Some(self.clone()))
+            } else {
+                (Some(self.clone()), None, None)
+            };
+        };
 
-        result
+        let before = (self.start < intersection.start).then_some(self.start..intersection.start);
+        let after = (intersection.end < self.end).then_some(intersection.end..self.end);
+
+        (before, Some(intersection), after)
+    }
+}
+
+// two ranges from a start always intersect, only a bounded piece can be left before the other start
+impl<T: Ord + Copy> RangeExt for RangeFrom<T> {
+    type Piece = Range<T>;
+
+    fn intersect(&self, other: &Self) -> Option<Self> {
+        Some(std::cmp::max(self.start, other.start)..)
+    }
+
+    fn join(&self, other: &Self) -> (Self, Option<Self>) {
+        (std::cmp::min(self.start, other.start).., None)
+    }
+
+    fn split_off(&self, other: &Self) -> (Option<Range<T>>, Option<Self>, Option<Range<T>>) {
+        let before = (self.start < other.start).then_some(self.start..other.start);
+        (before, self.intersect(other), None)
+    }
+}
+
+// two ranges up to an end always intersect, only a bounded piece can be left after the other end
+impl<T: Ord + Copy> RangeExt for RangeTo<T> {
+    type Piece = Range<T>;
+
+    fn intersect(&self, other: &Self) -> Option<Self> {
+        Some(..std::cmp::min(self.end, other.end))
+    }
+
+    fn join(&self, other: &Self) -> (Self, Option<Self>) {
+        (..std::cmp::max(self.end, other.end), None)
+    }
+
+    fn split_off(&self, other: &Self) -> (Option<Range<T>>, Option<Self>, Option<Range<T>>) {
+        let after = (other.end < self.end).then_some(other.end..self.end);
+        (None, self.intersect(other), after)
     }
 }
 
@@ -124,7 +176,266 @@ impl<T> RangeLength for RangeInclusive<T>
 where T: Sub<T, Output=T> + Increment<Output=T> + Copy {
     type Output = T;
 
+    // the length of a range covering all of T overflows
+    fn len(&self) -> Self::Output {
+        (*self.end() - *self.start()).inc()
+    }
+}
+
+impl<T> RangeLength for Range<T>
+where T: Sub<T, Output=T> + Copy {
+    type Output = T;
+
+    fn len(&self) -> Self::Output {
+        self.end - self.start
+    }
+}
+
+// a set of disjoint, non adjacent ranges kept sorted by their start
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeSet<T> {
+    ranges: Vec<RangeInclusive<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        RangeSet {
+            ranges: Vec::new(),
+        }
+    }
+}
+
+impl<T> RangeSet<T>
+where T: Ord + Copy + Decrement<Output = T> + Increment<Output = T>
+{
+    pub fn new() -> RangeSet<T> {
+        RangeSet::default()
+    }
+
+    pub fn ranges(&self) -> &[RangeInclusive<T>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=&RangeInclusive<T>> {
+        self.ranges.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // the smallest range containing the whole set
+    pub fn span(&self) -> Option<RangeInclusive<T>> {
+        Some(*self.ranges.first()?.start()..=*self.ranges.last()?.end())
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let idx = self.ranges.partition_point(|r| r.end() < value);
+        self.ranges.get(idx).is_some_and(|r| r.contains(value))
+    }
+
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        // adjacent ranges are merged as well, only stepping away from the other bound so T::MIN and T::MAX don't overflow
+        let lo = self.ranges.partition_point(|r| r.end() < range.start() && r.end().inc() < *range.start());
+        let hi = self.ranges.partition_point(|r| r.start() <= range.end() || r.start().dec() <= *range.end());
+
+        let mut merged = range;
+        if lo < hi {
+            let start = std::cmp::min(*merged.start(), *self.ranges[lo].start());
+            let end = std::cmp::max(*merged.end(), *self.ranges[hi - 1].end());
+            merged = start..=end;
+        }
+
+        self.ranges.splice(lo..hi, [merged]);
+    }
+
+    pub fn remove(&mut self, range: &RangeInclusive<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let lo = self.ranges.partition_point(|r| r.end() < range.start());
+        let hi = self.ranges.partition_point(|r| r.start() <= range.end());
+
+        let remaining: Vec<RangeInclusive<T>> = self.ranges[lo..hi].iter()
+            .flat_map(|r| {
+                r.subtract(range)
+            })
+            .flatten()
+            .collect();
+
+        self.ranges.splice(lo..hi, remaining);
+    }
+
+    pub fn union(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut result = self.clone();
+        for range in other.iter() {
+            result.insert(range.clone());
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let ranges = self.iter()
+            .flat_map(|r| other.iter().filter_map(|o| r.intersect(o)))
+            .collect();
+
+        RangeSet {
+            ranges,
+        }
+    }
+
+    pub fn difference(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut result = self.clone();
+        for range in other.iter() {
+            result.remove(range);
+        }
+        result
+    }
+
+    // the ranges within bounds that are not part of the set
+    pub fn gaps(&self, bounds: RangeInclusive<T>) -> impl Iterator<Item=RangeInclusive<T>> {
+        let mut gaps = Vec::new();
+        let mut cur = Some(*bounds.start());
+
+        for range in self.ranges.iter().filter_map(|r| r.intersect(&bounds)) {
+            if let Some(start) = cur.filter(|c| c < range.start()) {
+                gaps.push(start..=range.start().dec());
+            }
+            cur = (range.end() < bounds.end()).then(|| range.end().inc());
+        }
+
+        if let Some(start) = cur.filter(|c| c <= bounds.end()) {
+            gaps.push(start..=*bounds.end());
+        }
+
+        gaps.into_iter()
+    }
+}
+
+impl<T> RangeLength for RangeSet<T>
+where T: Sub<T, Output=T> + Add<T, Output=T> + Increment<Output=T> + Zero + Copy {
+    type Output = T;
+
     fn len(&self) -> Self::Output {
-        self.end().inc() - *self.start()
+        self.ranges.iter().fold(T::zero(), |acc, r| acc + r.len())
+    }
+}
+
+impl<T> FromIterator<RangeInclusive<T>> for RangeSet<T>
+where T: Ord + Copy + Decrement<Output = T> + Increment<Output = T>
+{
+    fn from_iter<I: IntoIterator<Item=RangeInclusive<T>>>(iter: I) -> Self {
+        let mut range_set = RangeSet::new();
+        for range in iter {
+            range_set.insert(range);
+        }
+        range_set
     }
 }
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_subtract() {
+        assert_eq!([Some(1..=3), Some(7..=9)], (1..=9).subtract(&(4..=6)));
+        assert_eq!([None, Some(5..=9)], (1..=9).subtract(&(0..=4)));
+        assert_eq!([Some(1..=9), None], (1..=9).subtract(&(10..=12)));
+        assert_eq!([None, Some(10..=12)], (10..=12).subtract(&(1..=9)));
+        assert_eq!([None, None], (4..=6).subtract(&(1..=9)));
+
+        assert_eq!([Some(1..4), Some(6..9)], (1..9).subtract(&(4..6)));
+        assert_eq!([Some(1..9), None], (1..9).subtract(&(9..12)));
+        assert_eq!([None, Some(9..12)], (9..12).subtract(&(1..9)));
+        assert_eq!([Some(1..4), None], (1..).subtract(&(4..)));
+        assert_eq!([None, None], (4..).subtract(&(1..)));
+        assert_eq!([None, Some(4..9)], (..9).subtract(&(..4)));
+        assert_eq!([None, None], (..4).subtract(&(..9)));
+    }
+
+    #[test]
+    fn test_split_off_disjoint() {
+        assert_eq!((Some(1..=3), None, None), (1..=3).split_off(&(5..=7)));
+        assert_eq!((None, None, Some(5..=7)), (5..=7).split_off(&(1..=3)));
+
+        assert_eq!((Some(1..3), None, None), (1..3).split_off(&(3..7)));
+        assert_eq!((None, None, Some(3..7)), (3..7).split_off(&(1..3)));
+
+        // unbounded ranges in the same direction always intersect, the pieces still land on their side
+        assert_eq!((Some(1..5), Some(5..), None), (1..).split_off(&(5..)));
+        assert_eq!((None, Some(5..), None), (5..).split_off(&(1..)));
+        assert_eq!((None, Some(..1), Some(1..5)), (..5).split_off(&(..1)));
+        assert_eq!((None, Some(..1), None), (..1).split_off(&(..5)));
+    }
+
+    #[test]
+    fn test_half_open_and_unbounded() {
+        assert_eq!(None, (1..4).intersect(&(4..6)));
+        assert!((1..5).contains_fully(&(2..4)));
+        assert!(!(1..5).contains_fully(&(4..8)));
+        assert_eq!((1..8, None), (1..5).join(&(4..8)));
+        assert_eq!(3, RangeLength::len(&(1..4)));
+
+        assert_eq!(Some(4..), (1..).intersect(&(4..)));
+        assert!((1..).contains_fully(&(4..)));
+        assert_eq!((1.., None), (4..).join(&(1..)));
+        assert_eq!(Some(..4), (..9).intersect(&(..4)));
+        assert!((..9).contains_fully(&(..4)));
+    }
+
+    #[test]
+    fn test_range_set_insert_remove() {
+        let mut set: RangeSet<i64> = [5..=7, 1..=2, 3..=3, 10..=12].into_iter().collect();
+        assert_eq!(&[1..=3, 5..=7, 10..=12], set.ranges());
+        assert_eq!(9, set.len());
+
+        set.insert(4..=9);
+        assert_eq!(&[1..=12], set.ranges());
+
+        set.remove(&(4..=5));
+        set.remove(&(12..=20));
+        assert_eq!(&[1..=3, 6..=11], set.ranges());
+        assert!(set.contains(&6) && set.contains(&3));
+        assert!(!set.contains(&4) && !set.contains(&12) && !set.contains(&0));
+        assert_eq!(Some(1..=11), set.span());
+
+        assert_eq!(vec![-1..=0, 4..=5, 12..=13], set.gaps(-1..=13).collect::<Vec<_>>());
+        assert_eq!(vec![4..=5], set.gaps(2..=8).collect::<Vec<_>>());
+        assert_eq!(0, set.gaps(7..=9).count());
+    }
+
+    #[test]
+    fn test_range_set_bounds() {
+        let mut set: RangeSet<u8> = RangeSet::new();
+        set.insert(200..=u8::MAX);
+        set.insert(0..=9);
+        set.insert(190..=199);
+        set.insert(10..=10);
+        assert_eq!(&[0..=10, 190..=u8::MAX], set.ranges());
+        assert_eq!(77, set.len());
+        assert_eq!(vec![11..=189], set.gaps(0..=u8::MAX).collect::<Vec<_>>());
+
+        set.remove(&(250..=u8::MAX));
+        assert_eq!(&[0..=10, 190..=249], set.ranges());
+        assert_eq!(vec![250..=u8::MAX], set.gaps(240..=u8::MAX).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_range_set_ops() {
+        let a: RangeSet<i32> = [1..=5, 10..=15].into_iter().collect();
+        let b: RangeSet<i32> = [4..=11].into_iter().collect();
+
+        assert_eq!(&[1..=15], a.union(&b).ranges());
+        assert_eq!(&[4..=5, 10..=11], a.intersection(&b).ranges());
+        assert_eq!(&[1..=3, 12..=15], a.difference(&b).ranges());
+        assert_eq!(&[6..=9], b.difference(&a).ranges());
+    }
+
+}
@@ -1,11 +1,13 @@
 use std::fmt::{Debug, Formatter};
-use std::mem::replace;
+use std::ops::{Index, IndexMut};
 use crate::utils::Error;
 
+// a deque with a fixed capacity, pushing into a full buffer evicts from the other end
 pub struct RingBuf<T> {
-    buffer: Vec<T>,
+    buffer: Vec<Option<T>>,
     size: usize,
     cur: usize,
+    len: usize,
 }
 
 impl<T: Debug> Debug for RingBuf<T> {
@@ -18,25 +20,105 @@ impl<T> RingBuf<T> {
 
     pub fn new(size: usize) -> RingBuf<T> {
         RingBuf {
-            buffer: Vec::new(),
+            buffer: (0..size).map(|_| None).collect(),
             size,
             cur: 0,
+            len: 0,
         }
     }
 
     pub fn push(&mut self, value: T) -> Option<T> {
+        self.push_back(value)
+    }
+
+    pub fn push_back(&mut self, value: T) -> Option<T> {
+        if self.size == 0 {
+            return Some(value);
+        }
+
+        let evicted = if self.len == self.size {
+            self.pop_front()
+        } else {
+            None
+        };
+
+        let end = (self.cur + self.len) % self.size;
+        self.buffer[end] = Some(value);
+        self.len += 1;
+
+        evicted
+    }
+
+    pub fn push_front(&mut self, value: T) -> Option<T> {
+        if self.size == 0 {
+            return Some(value);
+        }
 
-        if self.buffer.len() < self.size {
-            self.buffer.push(value);
+        let evicted = if self.len == self.size {
+            self.pop_back()
+        } else {
+            None
+        };
+
+        self.cur = (self.cur + self.size - 1) % self.size;
+        self.buffer[self.cur] = Some(value);
+        self.len += 1;
+
+        evicted
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
             return None;
         }
 
-        let evicted = replace(&mut self.buffer[self.cur], value);
+        let value = self.buffer[self.cur].take();
+        self.cur = (self.cur + 1) % self.size;
+        self.len -= 1;
 
-        self.cur += 1;
-        self.cur %= self.size;
+        value
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
 
-        Some(evicted)
+        self.len -= 1;
+        let end = (self.cur + self.len) % self.size;
+        self.buffer[end].take()
+    }
+
+    // moves the first n elements to the back
+    pub fn rotate_left(&mut self, n: usize) {
+        if self.len == 0 {
+            return;
+        }
+
+        let n = n % self.len;
+        if self.len == self.size {
+            self.cur = (self.cur + n) % self.size;
+            return;
+        }
+
+        for _ in 0..n {
+            if let Some(value) = self.pop_front() {
+                self.push_back(value);
+            }
+        }
+    }
+
+    // moves the last n elements to the front
+    pub fn rotate_right(&mut self, n: usize) {
+        if self.len > 0 {
+            self.rotate_left(self.len - n % self.len);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.buffer.iter_mut().for_each(|v| *v = None);
+        self.cur = 0;
+        self.len = 0;
     }
 
     pub fn iter(&self) -> RingBufIterator<'_, T> {
@@ -46,41 +128,42 @@ impl<T> RingBuf<T> {
         }
     }
 
+    pub fn iter_mut(&mut self) -> impl Iterator<Item=&mut T> {
+        let (wrapped, front) = self.buffer.split_at_mut(self.cur);
+        front.iter_mut().chain(wrapped.iter_mut())
+            .take(self.len)
+            .filter_map(Option::as_mut)
+    }
+
     pub fn get(&self, idx: usize) -> Option<&T> {
         self.adjust_index(idx)
             .ok()
-            .map(|i| &self.buffer[i])
+            .and_then(|i| self.buffer[i].as_ref())
     }
 
     pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
         self.adjust_index(idx)
             .ok()
-            .map(|i| &mut self.buffer[i])
+            .and_then(|i| self.buffer[i].as_mut())
     }
 
     pub fn set(&mut self, idx: usize, value: T) -> Option<T> {
         match self.adjust_index(idx) {
-            Ok(i) => {
-                let previous = replace(&mut self.buffer[i], value);
-                Some(previous)
-            },
+            Ok(i) => self.buffer[i].replace(value),
             Err(_) => None,
         }
     }
 
-    pub fn swap(&mut self, idx1: usize, idx2: usize) {
-        let ai1 = self.adjust_index(idx1).unwrap();
-        let ai2 = self.adjust_index(idx2).unwrap();
+    pub fn swap(&mut self, idx1: usize, idx2: usize) -> Result<(), Error> {
+        let ai1 = self.adjust_index(idx1)?;
+        let ai2 = self.adjust_index(idx2)?;
         self.buffer.swap(ai1, ai2);
+        Ok(())
     }
 
     pub fn check_index(&self, idx: usize) -> Result<(), Error> {
-        if idx > self.size {
-            return Err(Error(format!("index out of bounds: {}/{}", idx, self.size)));
-        }
-
-        if idx >= self.buffer.len() {
-            return Err(Error(format!("index out of bounds: {}/{}", idx, self.buffer.len())));
+        if idx >= self.len {
+            return Err(Error::invalid_state(format!("index out of bounds: {}/{}", idx, self.len)));
         }
 
         Ok(())
@@ -91,19 +174,53 @@ impl<T> RingBuf<T> {
     }
 
     pub fn len(&self) -> usize {
-        std::cmp::min(self.size, self.buffer.len())
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.size
+    }
+
+}
+
+impl<T> Index<usize> for RingBuf<T> {
+    type Output = T;
+
+    fn index(&self, idx: usize) -> &Self::Output {
+        let len = self.len;
+        self.get(idx).unwrap_or_else(|| panic!("index out of bounds: {}/{}", idx, len))
     }
+}
 
+impl<T> IndexMut<usize> for RingBuf<T> {
+    fn index_mut(&mut self, idx: usize) -> &mut Self::Output {
+        let len = self.len;
+        self.get_mut(idx).unwrap_or_else(|| panic!("index out of bounds: {}/{}", idx, len))
+    }
 }
 
 impl<E> FromIterator<E> for RingBuf<E> {
     fn from_iter<T: IntoIterator<Item=E>>(iter: T) -> Self {
-        let buffer: Vec<E> = iter.into_iter().collect();
+        let buffer: Vec<Option<E>> = iter.into_iter().map(Some).collect();
         let size = buffer.len();
         RingBuf {
             buffer,
             size,
             cur: 0,
+            len: size,
+        }
+    }
+}
+
+// pushes to the back, evicting from the front once the buffer is full
+impl<E> Extend<E> for RingBuf<E> {
+    fn extend<T: IntoIterator<Item=E>>(&mut self, iter: T) {
+        for value in iter {
+            self.push_back(value);
         }
     }
 }
@@ -117,15 +234,41 @@ impl<'a, T: 'a> Iterator for RingBufIterator<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let item = match self.ring_buf.adjust_index(self.cur) {
-            Ok(ai) => Some(&self.ring_buf.buffer[ai]),
-            Err(_) => None,
-        };
+        let item = self.ring_buf.get(self.cur);
         self.cur += 1;
         item
     }
 }
 
+pub struct RingBufIntoIterator<T>(RingBuf<T>);
+
+impl<T> Iterator for RingBufIntoIterator<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let RingBufIntoIterator(ring_buf) = self;
+        ring_buf.pop_front()
+    }
+}
+
+impl<T> IntoIterator for RingBuf<T> {
+    type Item = T;
+    type IntoIter = RingBufIntoIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        RingBufIntoIterator(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RingBuf<T> {
+    type Item = &'a T;
+    type IntoIter = RingBufIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -162,10 +305,52 @@ mod test {
         let elements: Vec<&i32> = ring_buf.iter().collect();
         assert_eq!(vec![&2, &3, &4, &5, &6], elements);
 
-        ring_buf.swap(0, 1);
+        ring_buf.swap(0, 1).unwrap();
 
         let elements: Vec<&i32> = ring_buf.iter().collect();
         assert_eq!(vec![&3, &2, &4, &5, &6], elements);
+
+        assert!(ring_buf.swap(0, 5).is_err());
+        assert!(ring_buf.check_index(5).is_err());
+        assert_eq!(None, ring_buf.get(5));
+    }
+
+    #[test]
+    fn test_deque_ops() {
+        let mut ring_buf: RingBuf<i32> = RingBuf::new(4);
+
+        ring_buf.extend([1, 2, 3]);
+        assert_eq!(None, ring_buf.push_front(0));
+        assert_eq!(Some(3), ring_buf.push_front(-1));
+        assert_eq!(vec![-1, 0, 1, 2], ring_buf.iter().copied().collect::<Vec<_>>());
+
+        assert_eq!(Some(-1), ring_buf.pop_front());
+        assert_eq!(Some(2), ring_buf.pop_back());
+        assert_eq!(2, ring_buf.len());
+
+        ring_buf.extend([2, 3, 4]);
+        assert_eq!(vec![1, 2, 3, 4], ring_buf.iter().copied().collect::<Vec<_>>());
+
+        ring_buf.rotate_left(1);
+        assert_eq!(vec![2, 3, 4, 1], ring_buf.iter().copied().collect::<Vec<_>>());
+        ring_buf.rotate_right(3);
+        assert_eq!(vec![3, 4, 1, 2], ring_buf.iter().copied().collect::<Vec<_>>());
+
+        ring_buf.pop_back();
+        ring_buf.rotate_left(5);
+        assert_eq!(vec![1, 3, 4], ring_buf.iter().copied().collect::<Vec<_>>());
+
+        for value in ring_buf.iter_mut() {
+            *value *= 10;
+        }
+        ring_buf[0] += 1;
+        assert_eq!(11, ring_buf[0]);
+        assert_eq!(vec![11, 30, 40], ring_buf.into_iter().collect::<Vec<_>>());
+
+        let mut ring_buf: RingBuf<i32> = (1..=3).collect();
+        ring_buf.clear();
+        assert!(ring_buf.is_empty());
+        assert_eq!(None, ring_buf.pop_back());
     }
 
 }
@@ -1,10 +1,10 @@
-use std::cmp::Ordering;
+use std::cmp::{max, Ordering};
 use std::fmt::{Debug, Formatter};
 use std::hash::{Hash, Hasher};
-use std::ops::{Add, BitOr, Mul, RangeInclusive, Sub};
+use std::ops::{Add, AddAssign, BitOr, Div, Mul, Neg, Sub, SubAssign};
 use std::str::FromStr;
 use crate::utils::{Error, Surroundings};
-use crate::utils::num::{Decrement, Increment};
+use crate::utils::num::{Decrement, Increment, Integer};
 use crate::utils::vec3::Vec3;
 
 pub struct Vec2<T>(pub T, pub T);
@@ -125,9 +125,9 @@ impl<T: FromStr> FromStr for Vec2<T> {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (x, y) = s.split_once(",").ok_or(Error(format!("cannot parse vec2: {}", s)))?;
-        let xp = x.parse().map_err(|_| Error(format!("cannot parse x {}", x)))?;
-        let yp = y.parse().map_err(|_| Error(format!("cannot parse y {}", y)))?;
+        let (x, y) = s.split_once(",").ok_or_else(|| Error::parse(format!("cannot parse vec2: {}", s)))?;
+        let xp = x.parse().map_err(|_| Error::cannot_parse_at(s, x))?;
+        let yp = y.parse().map_err(|_| Error::cannot_parse_at(s, y))?;
         Ok(Vec2(xp, yp))
     }
 }
@@ -175,8 +175,103 @@ impl<T: Sub<Output=T>> Sub for Vec2<T> {
     }
 }
 
+impl<T: AddAssign> AddAssign for Vec2<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        let Vec2(lx, ly) = self;
+        let Vec2(rx, ry) = rhs;
+
+        *lx += rx;
+        *ly += ry;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Vec2<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        let Vec2(lx, ly) = self;
+        let Vec2(rx, ry) = rhs;
+
+        *lx -= rx;
+        *ly -= ry;
+    }
+}
+
+impl<T: Mul<Output=T> + Copy> Mul<T> for Vec2<T> {
+    type Output = Vec2<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        let Vec2(x, y) = self;
+        Vec2(x * rhs, y * rhs)
+    }
+}
+
+impl<T: Div<Output=T> + Copy> Div<T> for Vec2<T> {
+    type Output = Vec2<T>;
+
+    fn div(self, rhs: T) -> Self::Output {
+        let Vec2(x, y) = self;
+        Vec2(x / rhs, y / rhs)
+    }
+}
+
+impl<T: Neg<Output=T>> Neg for Vec2<T> {
+    type Output = Vec2<T>;
+
+    fn neg(self) -> Self::Output {
+        let Vec2(x, y) = self;
+        Vec2(-x, -y)
+    }
+}
+
+impl<T: Integer> Vec2<T> {
+
+    pub fn signum(&self) -> Vec2<T> {
+        self.map(|v| match v.cmp(&T::zero()) {
+            Ordering::Less => T::zero() - T::one(),
+            Ordering::Equal => T::zero(),
+            Ordering::Greater => T::one(),
+        })
+    }
+
+    pub fn dot(&self, rhs: &Vec2<T>) -> T {
+        let (Vec2(lx, ly), Vec2(rx, ry)) = (self, rhs);
+        *lx * *rx + *ly * *ry
+    }
+
+    // the z component of the 3d cross product
+    pub fn cross(&self, rhs: &Vec2<T>) -> T {
+        let (Vec2(lx, ly), Vec2(rx, ry)) = (self, rhs);
+        *lx * *ry - *ly * *rx
+    }
+
+}
+
+// rotations by 90 degrees, counter clockwise is meant with the y axis pointing up
+impl<T: Neg<Output=T> + Copy> Vec2<T> {
+
+    pub fn rotate_ccw(&self) -> Vec2<T> {
+        let Vec2(x, y) = self;
+        Vec2(-*y, *x)
+    }
+
+    pub fn rotate_cw(&self) -> Vec2<T> {
+        let Vec2(x, y) = self;
+        Vec2(*y, -*x)
+    }
+
+}
+
 impl<T: Sub<Output=T> + Add<Output=T> + Ord> Vec2<T> {
 
+    pub fn chebyshev_dist(self, rhs: Vec2<T>) -> T {
+        let Vec2(lx, ly) = self;
+        let Vec2(rx, ry) = rhs;
+
+        let dist_x = if lx > rx { lx - rx } else { rx - lx };
+        let dist_y = if ly > ry { ly - ry } else { ry - ly };
+
+        max(dist_x, dist_y)
+    }
+
     pub fn manhattan_dist(self, rhs: Vec2<T>) -> T {
         let Vec2(lx, ly) = self;
         let Vec2(rx, ry) = rhs;
@@ -225,117 +320,65 @@ impl<T: Copy + Increment<Output=T> + Decrement<Output=T> + ?Sized> Surroundings<
     }
 }
 
-pub struct BoundingBox<T> {
-    top_left: Vec2<T>,
-    bottom_right: Vec2<T>,
-}
-
-impl<T> BoundingBox<T> {
-    pub fn new(top_left: Vec2<T>, bottom_right: Vec2<T>) -> BoundingBox<T> {
-        BoundingBox {
-            top_left,
-            bottom_right,
-        }
-    }
-}
-
-impl<'a, E: PartialOrd> FromIterator<&'a Vec2<E>> for BoundingBox<&'a E> {
-    fn from_iter<T: IntoIterator<Item=&'a Vec2<E>>>(iter: T) -> Self {
-        let mut iter = iter.into_iter();
-        let first_item = iter.next();
-        let first_item = first_item.expect("can only compute bounding box for non empty iterators");
-
-        let mut min_x = first_item.get_x();
-        let mut max_x = min_x;
-
-        let mut min_y = first_item.get_y();
-        let mut max_y = min_y;
-
-        for item in iter {
-            let x = item.get_x();
-            let y = item.get_y();
-
-            if x < min_x {
-                min_x = x;
-            }
-            if x > max_x {
-                max_x = x;
-            }
-            if y < min_y {
-                min_y = y;
-            }
-            if y > max_y {
-                max_y = y;
-            }
-        }
-
-        BoundingBox {
-            top_left: Vec2(min_x, min_y),
-            bottom_right: Vec2(max_x, max_y),
-        }
+impl<T: Copy + Increment<Output=T> + Decrement<Output=T>> Surroundings<8> for Vec2<T> {
+    fn get_surroundings(&self) -> [Self; 8] where Self: Sized {
+        let Vec2(x, y) = self;
+        [
+            Vec2(x.dec(), y.dec()),
+            Vec2(*x, y.dec()),
+            Vec2(x.inc(), y.dec()),
+            Vec2(x.dec(), *y),
+            Vec2(x.inc(), *y),
+            Vec2(x.dec(), y.inc()),
+            Vec2(*x, y.inc()),
+            Vec2(x.inc(), y.inc()),
+        ]
     }
 }
 
-impl<T> BoundingBox<T> {
-
-    pub fn top_left(&self) -> &Vec2<T> {
-        &self.top_left
-    }
-
-    pub fn bottom_right(&self) -> &Vec2<T> {
-        &self.bottom_right
-    }
-
-    pub fn top_right(&self) -> Vec2<&T> {
-        Vec2(self.bottom_right.get_x(), self.top_left.get_y())
-    }
-
-    pub fn bottom_left(&self) -> Vec2<&T> {
-        Vec2(self.top_left.get_x(), self.bottom_right.get_y())
-    }
+#[cfg(test)]
+mod test {
 
-    pub fn map<K, F>(self, mapper: F) -> BoundingBox<K>
-        where F: Fn(T) -> K {
+    use super::*;
 
-        let Vec2(min_x, min_y) = self.top_left;
-        let Vec2(max_x, max_y) = self.bottom_right;
+    #[test]
+    fn test_operators() {
+        let mut v = Vec2(3, -4);
 
-        BoundingBox {
-            top_left: Vec2(mapper(min_x), mapper(min_y)),
-            bottom_right: Vec2(mapper(max_x), mapper(max_y))
-        }
-    }
-}
+        assert_eq!(Vec2(6, -8), v.clone() * 2);
+        assert_eq!(Vec2(1, -2), v.clone() / 2);
+        assert_eq!(Vec2(-3, 4), -v.clone());
+        assert_eq!(Vec2(1, -1), v.signum());
+        assert_eq!(Vec2(0, 1), Vec2(0u32, 7).signum());
 
-impl<T: Debug> Debug for BoundingBox<T> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "BoundingBox({:?}, {:?})", self.top_left, self.bottom_right)
+        v += Vec2(1, 1);
+        assert_eq!(Vec2(4, -3), v);
+        v -= Vec2(4, 0);
+        assert_eq!(Vec2(0, -3), v);
     }
-}
 
-impl<T: Copy> BoundingBox<T> {
+    #[test]
+    fn test_geometry() {
+        let a = Vec2(2, 1);
+        let b = Vec2(-1, 3);
 
-    pub fn y_range(&self) -> RangeInclusive<T> {
-        *self.top_left.get_y()..=*self.bottom_right.get_y()
+        assert_eq!(1, a.dot(&b));
+        assert_eq!(7, a.cross(&b));
+        assert_eq!(Vec2(-1, 2), a.rotate_ccw());
+        assert_eq!(Vec2(1, -2), a.rotate_cw());
+        assert_eq!(a, a.rotate_cw().rotate_cw().rotate_cw().rotate_cw());
+        assert_eq!(3, a.clone().chebyshev_dist(b.clone()));
+        assert_eq!(5, a | b);
     }
 
-    pub fn x_range(&self) -> RangeInclusive<T> {
-        *self.top_left.get_x()..=*self.bottom_right.get_x()
-    }
-
-}
-
-impl<T: PartialOrd> BoundingBox<T> {
-
-    pub fn contains(&self, v: &Vec2<T>) -> bool {
-        let Vec2(x, y) = v;
-        let Vec2(min_x, min_y) = &self.top_left;
-        let Vec2(max_x, max_y) = &self.bottom_right;
+    #[test]
+    fn test_surroundings() {
+        let neighbours: [Vec2<i32>; 8] = Vec2(0, 0).get_surroundings();
+        assert!(neighbours.iter().all(|n| n.clone().chebyshev_dist(Vec2(0, 0)) == 1));
+        assert_eq!(8, neighbours.iter().collect::<std::collections::HashSet<_>>().len());
 
-        x >= min_x &&
-            x <= max_x &&
-            y >= min_y &&
-            y <= max_y
+        let direct: [Vec2<i32>; 4] = Vec2(0, 0).get_surroundings();
+        assert!(direct.iter().all(|n| neighbours.contains(n)));
     }
 
 }
@@ -1,7 +1,7 @@
 use std::cmp::Ordering;
 use std::fmt::{Debug, Formatter};
 use std::hash::{Hash, Hasher};
-use std::ops::{Add, Sub};
+use std::ops::{Add, Neg, Sub};
 use std::str::FromStr;
 use crate::utils::{Error, Surroundings};
 use crate::utils::num::{Decrement, Increment};
@@ -161,9 +161,12 @@ impl<T: FromStr> FromStr for Vec3<T> {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let parts: Vec<&str> = s.splitn(3, ",").collect();
-        let xp = parts[0].parse().map_err(|_| Error(format!("cannot parse x {}", parts[0])))?;
-        let yp = parts[1].parse().map_err(|_| Error(format!("cannot parse y {}", parts[1])))?;
-        let zp = parts[2].parse().map_err(|_| Error(format!("cannot parse y {}", parts[2])))?;
+        let [x, y, z] = parts[..] else {
+            return Err(Error::parse(format!("cannot parse vec3: {}", s)));
+        };
+        let xp = x.parse().map_err(|_| Error::cannot_parse_at(s, x))?;
+        let yp = y.parse().map_err(|_| Error::cannot_parse_at(s, y))?;
+        let zp = z.parse().map_err(|_| Error::cannot_parse_at(s, z))?;
         Ok(Vec3(xp, yp, zp))
     }
 }
@@ -204,6 +207,62 @@ impl<T: Sub<Output=T>> Sub for Vec3<T> {
     }
 }
 
+impl<T: Sub<Output=T> + Add<Output=T> + Ord> Vec3<T> {
+
+    pub fn manhattan_dist(self, rhs: Vec3<T>) -> T {
+        let Vec3(lx, ly, lz) = self;
+        let Vec3(rx, ry, rz) = rhs;
+
+        let dist_x = if lx > rx { lx - rx } else { rx - lx };
+        let dist_y = if ly > ry { ly - ry } else { ry - ly };
+        let dist_z = if lz > rz { lz - rz } else { rz - lz };
+
+        dist_x + dist_y + dist_z
+    }
+}
+
+// rotations by 90 degrees counter clockwise around an axis, looking from its positive end
+impl<T: Neg<Output=T> + Copy> Vec3<T> {
+
+    pub fn rotate_x(&self) -> Vec3<T> {
+        let Vec3(x, y, z) = self;
+        Vec3(*x, -*z, *y)
+    }
+
+    pub fn rotate_y(&self) -> Vec3<T> {
+        let Vec3(x, y, z) = self;
+        Vec3(*z, *y, -*x)
+    }
+
+    pub fn rotate_z(&self) -> Vec3<T> {
+        let Vec3(x, y, z) = self;
+        Vec3(-*y, *x, *z)
+    }
+
+    // the vector in all 24 proper orientations, the first one is the vector itself
+    pub fn orientations(&self) -> [Vec3<T>; 24] {
+        let mut rolled = self.clone();
+        let mut rolls = Vec::with_capacity(4);
+        for _ in 0..4 {
+            rolls.push(rolled.clone());
+            rolled = rolled.rotate_x();
+        }
+
+        // turns the x axis towards each of the six directions
+        std::array::from_fn(|i| {
+            let v = &rolls[i % 4];
+            match i / 4 {
+                0 => v.clone(),
+                1 => v.rotate_y(),
+                2 => v.rotate_y().rotate_y(),
+                3 => v.rotate_y().rotate_y().rotate_y(),
+                4 => v.rotate_z(),
+                _ => v.rotate_z().rotate_z().rotate_z(),
+            }
+        })
+    }
+}
+
 impl<T: Hash> Hash for Vec3<T> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         let Vec3(x, y, z) = self;
@@ -226,3 +285,56 @@ impl<T: Copy + Increment<Output=T> + Decrement<Output=T> + ?Sized> Surroundings<
         ]
     }
 }
+
+impl<T: Copy + Increment<Output=T> + Decrement<Output=T>> Surroundings<26> for Vec3<T> {
+    fn get_surroundings(&self) -> [Self; 26] where Self: Sized {
+        let Vec3(x, y, z) = self;
+        let offsets = |v: &T| [v.dec(), *v, v.inc()];
+
+        let mut result = Vec::with_capacity(26);
+        for (i, nx) in offsets(x).into_iter().enumerate() {
+            for (j, ny) in offsets(y).into_iter().enumerate() {
+                for (k, nz) in offsets(z).into_iter().enumerate() {
+                    if (i, j, k) != (1, 1, 1) {
+                        result.push(Vec3(nx, ny, nz));
+                    }
+                }
+            }
+        }
+
+        std::array::from_fn(|i| result[i].clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::collections::HashSet;
+    use super::*;
+
+    #[test]
+    fn test_rotations() {
+        let v = Vec3(1, 2, 3);
+
+        assert_eq!(Vec3(1, -3, 2), v.rotate_x());
+        assert_eq!(Vec3(3, 2, -1), v.rotate_y());
+        assert_eq!(Vec3(-2, 1, 3), v.rotate_z());
+        assert_eq!(v, v.rotate_z().rotate_z().rotate_z().rotate_z());
+
+        let orientations = v.orientations();
+        assert_eq!(v, orientations[0]);
+        assert_eq!(24, orientations.iter().collect::<HashSet<_>>().len());
+        assert!(orientations.contains(&v.rotate_x().rotate_y().rotate_z()));
+        // a mirrored vector is not a proper rotation
+        assert!(!orientations.contains(&Vec3(-1, 2, 3)));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let neighbours: [Vec3<i32>; 26] = Vec3(0, 0, 0).get_surroundings();
+        assert_eq!(26, neighbours.iter().collect::<HashSet<_>>().len());
+        assert!(!neighbours.contains(&Vec3(0, 0, 0)));
+        assert_eq!(6, neighbours.iter().filter(|n| (*n).clone().manhattan_dist(Vec3(0, 0, 0)) == 1).count());
+    }
+
+}
@@ -0,0 +1,281 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::str::FromStr;
+use advent_of_code_2022::solver::{DayEntry, Part};
+use advent_of_code_2022::utils::Error;
+
+pub const DEFAULT_ANSWERS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/res/answers.txt");
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Expectation {
+    pub input_name: String,
+    pub part: Part,
+    pub answer: String,
+}
+
+impl FromStr for Expectation {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut split = s.trim().splitn(3, char::is_whitespace);
+
+        let (input_name, part, answer) = match (split.next(), split.next(), split.next()) {
+            (Some(input_name), Some(part), Some(answer)) => (input_name, part, answer),
+            _ => return Err(Error::cannot_parse(s)),
+        };
+
+        Ok(Expectation {
+            input_name: input_name.to_string(),
+            part: part.parse()?,
+            answer: unescape(answer.trim()),
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Manifest(Vec<Expectation>);
+
+impl Manifest {
+
+    pub fn load(path: &Path) -> Result<Manifest, Error> {
+        fs::read_to_string(path)
+            .map_err(|e| Error::io(format!("cannot read {}", path.display()), e))?
+            .parse()
+    }
+
+    pub fn get(&self, input_name: &str, part: Part) -> Option<&str> {
+        let Manifest(expectations) = self;
+        expectations.iter()
+            .find(|e| e.input_name == input_name && e.part == part)
+            .map(|e| e.answer.as_str())
+    }
+
+}
+
+impl FromStr for Manifest {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expectations: Result<Vec<Expectation>, Error> = s.lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .map(str::parse)
+            .collect();
+
+        Ok(Manifest(expectations?))
+    }
+}
+
+fn unescape(answer: &str) -> String {
+    answer.replace("\\n", "\n")
+}
+
+fn escape(answer: &str) -> String {
+    answer.replace('\n', "\\n")
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Outcome {
+    Pass,
+    Fail { expected: String, actual: Result<String, String> },
+    Missing { actual: Result<String, String> },
+}
+
+impl Outcome {
+
+    fn new(expected: Option<&str>, actual: Result<String, Error>) -> Outcome {
+        let actual = actual.map_err(|e| e.to_string());
+
+        match expected {
+            None => Outcome::Missing { actual },
+            Some(expected) if actual.as_deref() == Ok(expected) => Outcome::Pass,
+            Some(expected) => Outcome::Fail { expected: expected.to_string(), actual },
+        }
+    }
+
+}
+
+fn fmt_actual(actual: &Result<String, String>) -> String {
+    match actual {
+        Ok(answer) => escape(answer),
+        Err(message) => format!("error: {}", message),
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Pass => write!(f, "pass"),
+            Outcome::Fail { expected, actual } => write!(f, "FAIL (expected {}, got {})", escape(expected), fmt_actual(actual)),
+            Outcome::Missing { actual } => write!(f, "missing (got {})", fmt_actual(actual)),
+        }
+    }
+}
+
+fn catch_panic<T>(f: impl FnOnce() -> Result<T, Error>) -> Result<T, Error> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload.downcast_ref::<&str>().map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+
+        Err(Error::invalid_state(format!("panicked: {}", message)))
+    })
+}
+
+#[derive(Debug)]
+pub struct Verification {
+    pub day: usize,
+    pub input_name: String,
+    pub part: Part,
+    pub outcome: Outcome,
+}
+
+impl Display for Verification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day{} part{} {}: {}", self.day, self.part, self.input_name, self.outcome)
+    }
+}
+
+pub struct Verifier<'a> {
+    manifest: &'a Manifest,
+    res_dir: &'a Path,
+}
+
+impl<'a> Verifier<'a> {
+
+    pub fn new(manifest: &'a Manifest, res_dir: &'a Path) -> Verifier<'a> {
+        Verifier { manifest, res_dir }
+    }
+
+    fn example_names(&self, entry: &DayEntry) -> Result<Vec<String>, Error> {
+        let read_dir = fs::read_dir(self.res_dir)
+            .map_err(|e| Error::io(format!("cannot read {}", self.res_dir.display()), e))?;
+
+        let mut names: Vec<String> = read_dir
+            .filter_map(|e| e.ok())
+            .filter_map(|e| e.file_name().into_string().ok())
+            .filter(|name| entry.is_example_of(name))
+            .collect();
+
+        names.sort();
+        Ok(names)
+    }
+
+    fn verify_input(&self, entry: &DayEntry, input_name: &str, input: Result<String, Error>, parts: &[Part]) -> Vec<Verification> {
+        let puzzle = input.and_then(|input| catch_panic(|| entry.parse(&input)));
+
+        parts.iter().map(|part| {
+            let actual = match &puzzle {
+                Ok(puzzle) => catch_panic(|| puzzle.solve(*part)),
+                Err(e) => Err(Error::parse(format!("cannot parse input: {}", e))),
+            };
+
+            Verification {
+                day: entry.day(),
+                input_name: input_name.to_string(),
+                part: *part,
+                outcome: Outcome::new(self.manifest.get(input_name, *part), actual),
+            }
+        }).collect()
+    }
+
+    pub fn verify_day(&self, entry: &DayEntry, parts: &[Part]) -> Result<Vec<Verification>, Error> {
+        let mut verifications = self.verify_input(entry, entry.input_name(), Ok(entry.input().to_string()), parts);
+
+        for example_name in self.example_names(entry)? {
+            let path = self.res_dir.join(&example_name);
+            let input = fs::read_to_string(&path).map_err(|e| Error::io(format!("cannot read {}", path.display()), e));
+            verifications.extend(self.verify_input(entry, &example_name, input, parts));
+        }
+
+        Ok(verifications)
+    }
+
+}
+
+#[derive(Debug, Default)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    pub missing: usize,
+}
+
+impl Summary {
+
+    pub fn add(&mut self, outcome: &Outcome) {
+        match outcome {
+            Outcome::Pass => self.passed += 1,
+            Outcome::Fail { .. } => self.failed += 1,
+            Outcome::Missing { .. } => self.missing += 1,
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.failed == 0
+    }
+
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "passed: {}, failed: {}, missing: {}", self.passed, self.failed, self.missing)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use advent_of_code_2022::solver::find_day;
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest: Manifest = "\
+            # comment
+
+            day1-calories.txt 1 72602
+            day10-instr_example2.txt 2 ##..\\n#..#
+        ".parse().unwrap();
+
+        assert_eq!(Some("72602"), manifest.get("day1-calories.txt", Part::One));
+        assert_eq!(None, manifest.get("day1-calories.txt", Part::Two));
+        assert_eq!(Some("##..\n#..#"), manifest.get("day10-instr_example2.txt", Part::Two));
+
+        assert!("day1-calories.txt 3 1".parse::<Manifest>().is_err());
+        assert!("day1-calories.txt 1".parse::<Manifest>().is_err());
+    }
+
+    #[test]
+    fn test_outcome() {
+        assert_eq!(Outcome::Pass, Outcome::new(Some("42"), Ok("42".to_string())));
+        assert!(matches!(Outcome::new(Some("42"), Ok("43".to_string())), Outcome::Fail { .. }));
+        assert!(matches!(Outcome::new(Some("42"), Err(Error::invalid_state("broken"))), Outcome::Fail { .. }));
+        assert!(matches!(Outcome::new(None, Ok("43".to_string())), Outcome::Missing { .. }));
+    }
+
+    #[test]
+    fn test_verify_day() {
+        let manifest: Manifest = "\
+            day1-calories.txt 1 72602
+            day1-calories_example.txt 1 24000
+            day1-calories_example.txt 2 0
+        ".parse().unwrap();
+
+        let res_dir = Path::new(DEFAULT_ANSWERS).parent().unwrap();
+        let verifier = Verifier::new(&manifest, res_dir);
+
+        let verifications = verifier.verify_day(&find_day(1).unwrap(), &[Part::One, Part::Two]).unwrap();
+
+        let mut summary = Summary::default();
+        verifications.iter().for_each(|v| summary.add(&v.outcome));
+
+        assert_eq!(4, verifications.len());
+        assert_eq!(2, summary.passed);
+        assert_eq!(1, summary.failed);
+        assert_eq!(1, summary.missing);
+        assert!(!summary.is_success());
+    }
+
+}